@@ -0,0 +1,64 @@
+/*
+整个 crate 统一使用的错误类型
+- Graph/LinkedList 的 try_ 系列方法返回 Result<_, Error>，遇到非法输入时返回错误而不是 panic
+- 不带 try_ 的方法保持原有行为（越界 panic，删除返回 bool），内部调用 try_ 版本
+*/
+
+use std::fmt;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    // 节点 index 不存在
+    UnknownNode(usize),
+    // 节点名不存在。节点名是泛型，不放进错误中
+    UnknownName,
+    // 边 (src_idx, dst_idx) 不存在
+    UnknownEdge(usize, usize),
+    // index 指向的位置已经被删除（孔洞）
+    StaleIndex(usize),
+    // 节点名已经被其它节点使用，存放的是占用该节点名的节点 index
+    DuplicateName(usize),
+    // 链表的位置超出了链表长度
+    OutOfRange(usize),
+    // 图中存在从起点可达的负环，存放环上的节点 index
+    NegativeCycle(Vec<usize>),
+    // 图不是二分图，存放一个奇数长度的环
+    NotBipartite(Vec<usize>),
+    // 算法要求无环，但图中有环，存放环上的节点 index
+    Cyclic(Vec<usize>),
+    // 算法参数取值不合法，存放参数名。参数可能是 f64，不放进错误中
+    InvalidParameter(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownNode(idx) => write!(f, "unknown node index {}", idx),
+            Error::UnknownName => write!(f, "unknown node name"),
+            Error::UnknownEdge(src_idx, dst_idx) => write!(f, "unknown edge {} -> {}", src_idx, dst_idx),
+            Error::StaleIndex(idx) => write!(f, "stale index {}", idx),
+            Error::DuplicateName(idx) => write!(f, "node name already used by node {}", idx),
+            Error::OutOfRange(num) => write!(f, "position {} out of range", num),
+            Error::NegativeCycle(cycle) => write!(f, "negative cycle {:?}", cycle),
+            Error::NotBipartite(cycle) => write!(f, "not bipartite, odd cycle {:?}", cycle),
+            Error::Cyclic(cycle) => write!(f, "graph has cycle {:?}", cycle),
+            Error::InvalidParameter(name) => write!(f, "invalid parameter {}", name),
+        }
+    }
+}
+
+impl Error {
+    // 错误中携带的环（NegativeCycle、NotBipartite、Cyclic），其它错误返回 None
+    pub fn cycle(&self) -> Option<&[usize]> {
+        match self {
+            Error::NegativeCycle(cycle) | Error::NotBipartite(cycle) | Error::Cyclic(cycle) => Some(cycle),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
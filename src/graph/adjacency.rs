@@ -0,0 +1,430 @@
+/*
+图的邻接关系，Graph（HashSet 存边）和 FrozenGraph（CSR 存边）都实现了它
+只依赖拓扑结构的算法写在这里，两种图都可以使用，调用前需要 use graph::Adjacency
+算法本身写在各自的模块里，这里的方法只做转发
+*/
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use crate::error::{Error, Result};
+use crate::union_find::UnionFind;
+use super::all_pairs::{self, DistanceMatrix};
+use super::bellman_ford::{self, ShortestPaths};
+use super::bipartite::{self, Bipartition};
+use super::community::{self, Communities, CommunityOptions};
+use super::flow::{self, MaxFlow};
+use super::mst::{self, SpanningForest};
+use super::{centrality, coloring, components, euler, stats, yen, Direction};
+use super::pagerank::{self, PageRankOptions};
+use super::sampling::{self, WalkOptions};
+use super::transitive::{self, Reachability};
+use super::traversal::{self, Bfs, Control, Dfs, VisitEvent};
+
+
+pub trait Adjacency {
+    // 节点个数，节点的 index 为 0..node_count()
+    fn node_count(&self) -> usize;
+
+    // 节点的下游（next_idx）
+    fn successors(&self, idx: usize) -> impl Iterator<Item = usize> + '_;
+
+    // 节点的上游（prev_idx）
+    fn predecessors(&self, idx: usize) -> impl Iterator<Item = usize> + '_;
+
+    // 是否有 src_idx -> dst_idx 的边，Graph 查 HashSet，FrozenGraph 二分查找
+    fn has_edge(&self, src_idx: usize, dst_idx: usize) -> bool {
+        self.successors(src_idx).any(|next_idx| next_idx == dst_idx)
+    }
+
+    // 获取所有的边，按 (src_idx, dst_idx) 排好序
+    fn get_all_edges(&self) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        for src_idx in 0..self.node_count() {
+            let start = res.len();
+            res.extend(self.successors(src_idx).map(|dst_idx| (src_idx, dst_idx)));
+            res[start..].sort_unstable();
+        }
+        res
+    }
+
+    // 找到某个节点的全部下游节点。返回的数据结构是 {level: [idx1, idx2, ...]} 的 HashMap，存放的是下游层数、节点号
+    fn get_downstream(&self, batch_idx: Vec<usize>, max_level: usize) -> HashMap<usize, Vec<usize>> {
+        let mut res: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut q: Vec<usize> = batch_idx.clone();
+        let mut searched = HashSet::new(); // 存放已经被遍历到的节点。用来排除掉环状节点
+        let mut level = 0;
+        while !q.is_empty() && level < max_level {
+            res.insert(level, q.clone());
+            searched.extend(q.clone());
+            q = q.iter().flat_map(|&node_idx| {
+                self.successors(node_idx)
+                    .filter(|next_idx| !searched.contains(next_idx))
+            }).collect();
+            level += 1;
+        }
+        res
+    }
+
+    // 计算两个节点之间的最短距离（跳数），距离不小于 max_level 时返回 None
+    // 双向 BFS：每次扩展较小的一侧，正向沿 successors，反向沿 predecessors，在中间相遇
+    fn get_shortest(&self, src_idx: usize, dst_idx: usize, max_level: usize) -> Option<usize> {
+        if max_level == 0 {
+            return None;
+        }
+        if src_idx == dst_idx {
+            return Some(0);
+        }
+        // 已经访问过的节点及其到起点（正向）/ 终点（反向）的距离
+        let mut fwd_seen = HashMap::from([(src_idx, 0)]);
+        let mut bwd_seen = HashMap::from([(dst_idx, 0)]);
+        let mut fwd_q = vec![src_idx];
+        let mut bwd_q = vec![dst_idx];
+        let (mut fwd_level, mut bwd_level) = (0, 0);
+
+        // 两侧已经扩展到 fwd_level、bwd_level 还没相遇，说明距离大于 fwd_level + bwd_level
+        while !fwd_q.is_empty() && !bwd_q.is_empty() && fwd_level + bwd_level + 1 < max_level {
+            let met = if fwd_q.len() <= bwd_q.len() {
+                fwd_level += 1;
+                expand_level(&mut fwd_q, &mut fwd_seen, &bwd_seen, fwd_level, |idx| self.successors(idx))
+            } else {
+                bwd_level += 1;
+                expand_level(&mut bwd_q, &mut bwd_seen, &fwd_seen, bwd_level, |idx| self.predecessors(idx))
+            };
+            // 一整层里取最小值，才能保证是最短距离
+            if met.is_some() {
+                return met;
+            }
+        }
+        None
+    }
+
+    // 所有节点两两之间的跳数，每个节点做一次 BFS。结果见 DistanceMatrix
+    fn all_pairs_bfs(&self) -> DistanceMatrix {
+        all_pairs::all_pairs_bfs(self)
+    }
+
+    // 深度优先遍历的事件迭代器，见 traversal
+    fn dfs(&self, starts: Vec<usize>) -> Dfs<'_, Self> {
+        Dfs::new(self, starts)
+    }
+
+    // 广度优先遍历的事件迭代器
+    fn bfs(&self, starts: Vec<usize>) -> Bfs<'_, Self> {
+        Bfs::new(self, starts)
+    }
+
+    // 深度优先遍历，每个事件调用一次 visitor，返回触发 Control::Stop 的事件
+    fn dfs_visit(&self, starts: Vec<usize>, visitor: impl FnMut(VisitEvent) -> Control) -> Option<VisitEvent> {
+        traversal::drive(self.dfs(starts), visitor)
+    }
+
+    fn bfs_visit(&self, starts: Vec<usize>, visitor: impl FnMut(VisitEvent) -> Control) -> Option<VisitEvent> {
+        traversal::drive(self.bfs(starts), visitor)
+    }
+
+    // 同 dfs，但先检查 starts，有不存在的节点时返回 Error::UnknownNode（dfs 会在遍历到它时 panic）
+    fn try_dfs(&self, starts: Vec<usize>) -> Result<Dfs<'_, Self>> {
+        for &idx in &starts {
+            check_idx(self, idx)?;
+        }
+        Ok(self.dfs(starts))
+    }
+
+    fn try_bfs(&self, starts: Vec<usize>) -> Result<Bfs<'_, Self>> {
+        for &idx in &starts {
+            check_idx(self, idx)?;
+        }
+        Ok(self.bfs(starts))
+    }
+
+    fn try_dfs_visit(&self, starts: Vec<usize>, visitor: impl FnMut(VisitEvent) -> Control) -> Result<Option<VisitEvent>> {
+        Ok(traversal::drive(self.try_dfs(starts)?, visitor))
+    }
+
+    fn try_bfs_visit(&self, starts: Vec<usize>, visitor: impl FnMut(VisitEvent) -> Control) -> Result<Option<VisitEvent>> {
+        Ok(traversal::drive(self.try_bfs(starts)?, visitor))
+    }
+
+    // 按图中现有的边构建并查集，元素编号就是节点 index，见 components
+    fn to_union_find(&self) -> UnionFind {
+        components::to_union_find(self)
+    }
+
+    // 每个节点所在弱连通分量的编号，编号为 0..分量个数
+    fn component_ids(&self) -> Vec<usize> {
+        self.to_union_find().component_ids()
+    }
+
+    // 所有弱连通分量，每个分量内的节点 index 从小到大排列
+    fn weakly_connected_components(&self) -> Vec<Vec<usize>> {
+        self.to_union_find().groups()
+    }
+
+    // 中心性，见 centrality
+    fn in_degree_centrality(&self) -> Vec<f64> {
+        centrality::in_degree_centrality(self)
+    }
+
+    fn out_degree_centrality(&self) -> Vec<f64> {
+        centrality::out_degree_centrality(self)
+    }
+
+    fn closeness_centrality(&self) -> Vec<f64> {
+        centrality::closeness_centrality(self)
+    }
+
+    fn harmonic_centrality(&self) -> Vec<f64> {
+        centrality::harmonic_centrality(self)
+    }
+
+    fn betweenness_centrality(&self, sources: Option<&[usize]>, sample: Option<(usize, u64)>) -> Result<Vec<f64>> {
+        centrality::betweenness_centrality(self, sources, sample)
+    }
+
+    // 社区发现，见 community
+    fn modularity(&self, ids: &[usize], weight: impl Fn(usize, usize) -> f64) -> Result<f64> {
+        community::modularity(self, ids, weight)
+    }
+
+    fn label_propagation(&self, weight: impl Fn(usize, usize) -> f64, opts: &CommunityOptions) -> Communities {
+        community::label_propagation(self, weight, opts)
+    }
+
+    fn louvain(&self, weight: impl Fn(usize, usize) -> f64, opts: &CommunityOptions) -> Communities {
+        community::louvain(self, weight, opts)
+    }
+
+    // PageRank，见 pagerank
+    fn pagerank(&self, options: &PageRankOptions) -> Result<Vec<f64>> {
+        pagerank::pagerank(self, options)
+    }
+
+    fn personalized_pagerank(&self, seeds: &[usize], options: &PageRankOptions) -> Result<Vec<f64>> {
+        pagerank::personalized_pagerank(self, seeds, options)
+    }
+
+    // 结构统计，见 stats
+    fn core_numbers(&self) -> Vec<usize> {
+        stats::core_numbers(self)
+    }
+
+    fn k_core(&self, k: usize) -> Vec<usize> {
+        stats::k_core(self, k)
+    }
+
+    fn triangle_counts(&self) -> Vec<usize> {
+        stats::triangle_counts(self)
+    }
+
+    fn triangle_count(&self) -> usize {
+        stats::triangle_count(self)
+    }
+
+    fn local_clustering(&self) -> Vec<f64> {
+        stats::local_clustering(self)
+    }
+
+    fn average_clustering(&self) -> f64 {
+        stats::average_clustering(self)
+    }
+
+    fn global_clustering(&self) -> f64 {
+        stats::global_clustering(self)
+    }
+
+    fn degree_histogram(&self, direction: Direction) -> Vec<usize> {
+        stats::degree_histogram(self, direction)
+    }
+
+    // 最小生成树/森林，见 mst
+    fn mst_kruskal(&self, weight: impl Fn(usize, usize) -> f64) -> SpanningForest {
+        mst::mst_kruskal(self, weight)
+    }
+
+    fn mst_prim(&self, weight: impl Fn(usize, usize) -> f64) -> SpanningForest {
+        mst::mst_prim(self, weight)
+    }
+
+    // 含负权的单源最短路，见 bellman_ford
+    fn bellman_ford(&self, src_idx: usize, weight: impl Fn(usize, usize) -> f64) -> Result<ShortestPaths> {
+        bellman_ford::bellman_ford(self, src_idx, weight)
+    }
+
+    fn spfa(&self, src_idx: usize, weight: impl Fn(usize, usize) -> f64) -> Result<ShortestPaths> {
+        bellman_ford::spfa(self, src_idx, weight)
+    }
+
+    // 带权的全源最短路，见 all_pairs
+    fn floyd_warshall(&self, weight: impl Fn(usize, usize) -> f64) -> Result<DistanceMatrix> {
+        all_pairs::floyd_warshall(self, weight)
+    }
+
+    fn johnson(&self, weight: impl Fn(usize, usize) -> f64) -> Result<DistanceMatrix> {
+        all_pairs::johnson(self, weight)
+    }
+
+    // 前 k 条最短的简单路径，见 yen
+    fn k_shortest_paths(&self, src_idx: usize, dst_idx: usize, k: usize, weight: impl Fn(usize, usize) -> f64) -> Result<Vec<(Vec<usize>, f64)>> {
+        yen::k_shortest_paths(self, src_idx, dst_idx, k, weight)
+    }
+
+    // 网络流，见 flow
+    fn max_flow(&self, src_idx: usize, dst_idx: usize, capacity: impl Fn(usize, usize) -> f64) -> Result<MaxFlow> {
+        flow::max_flow(self, src_idx, dst_idx, capacity)
+    }
+
+    fn min_cost_max_flow(
+        &self,
+        src_idx: usize,
+        dst_idx: usize,
+        capacity: impl Fn(usize, usize) -> f64,
+        cost: impl Fn(usize, usize) -> f64,
+    ) -> Result<(MaxFlow, f64)> {
+        flow::min_cost_max_flow(self, src_idx, dst_idx, capacity, cost)
+    }
+
+    // 二分图，见 bipartite
+    fn bipartition(&self) -> Bipartition {
+        bipartite::bipartition(self)
+    }
+
+    fn max_matching(&self) -> Result<Vec<(usize, usize)>> {
+        bipartite::max_matching(self)
+    }
+
+    fn min_cost_assignment(&self, cost: impl Fn(usize, usize) -> f64) -> Result<(Vec<(usize, usize)>, f64)> {
+        bipartite::min_cost_assignment(self, cost)
+    }
+
+    // 着色与团，见 coloring
+    fn dsatur_coloring(&self) -> Vec<usize> {
+        coloring::dsatur_coloring(self)
+    }
+
+    fn welsh_powell_coloring(&self) -> Vec<usize> {
+        coloring::welsh_powell_coloring(self)
+    }
+
+    fn exact_coloring(&self, max_steps: usize) -> (Vec<usize>, bool) {
+        coloring::exact_coloring(self, max_steps)
+    }
+
+    fn maximal_independent_set(&self) -> Vec<usize> {
+        coloring::maximal_independent_set(self)
+    }
+
+    fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        coloring::maximal_cliques(self)
+    }
+
+    fn maximum_clique(&self) -> Vec<usize> {
+        coloring::maximum_clique(self)
+    }
+
+    // 欧拉路径，见 euler
+    fn has_eulerian_circuit(&self) -> bool {
+        euler::has_eulerian_circuit(self)
+    }
+
+    fn has_eulerian_path(&self) -> bool {
+        euler::has_eulerian_path(self)
+    }
+
+    fn eulerian_circuit(&self) -> Option<Vec<(usize, usize)>> {
+        euler::eulerian_circuit(self)
+    }
+
+    fn eulerian_path(&self) -> Option<Vec<(usize, usize)>> {
+        euler::eulerian_path(self)
+    }
+
+    // 有向无环图，见 transitive
+    fn topological_sort(&self) -> Result<Vec<usize>> {
+        transitive::topological_sort(self)
+    }
+
+    fn transitive_closure(&self) -> Result<Reachability> {
+        transitive::transitive_closure(self)
+    }
+
+    // 随机游走与采样，见 sampling
+    fn random_walks(&self, starts: &[usize], weight: impl Fn(usize, usize) -> f64, opts: &WalkOptions) -> Result<Vec<Vec<usize>>> {
+        sampling::random_walks(self, starts, weight, opts)
+    }
+
+    fn sample_nodes(&self, k: usize, seed: u64) -> Vec<usize> {
+        sampling::sample_nodes(self, k, seed)
+    }
+
+    fn sample_edges(&self, k: usize, seed: u64) -> Vec<(usize, usize)> {
+        sampling::sample_edges(self, k, seed)
+    }
+
+    fn snowball_sample(&self, seeds: &[usize], fanout: usize, max_nodes: usize, seed: u64) -> Result<Vec<usize>> {
+        sampling::snowball_sample(self, seeds, fanout, max_nodes, seed)
+    }
+}
+
+
+// 节点 index 越界时返回 Error::UnknownNode，供写在 Adjacency 上的 try 风格算法使用
+pub(super) fn check_idx<G: Adjacency + ?Sized>(graph: &G, idx: usize) -> Result<()> {
+    if idx < graph.node_count() { Ok(()) } else { Err(Error::UnknownNode(idx)) }
+}
+
+
+// 把 q 扩展一层（新节点的距离为 level），返回与另一侧相遇时的最短距离
+fn expand_level<I: Iterator<Item = usize>>(
+    q: &mut Vec<usize>,
+    seen: &mut HashMap<usize, usize>,
+    other_seen: &HashMap<usize, usize>,
+    level: usize,
+    neighbors: impl Fn(usize) -> I,
+) -> Option<usize> {
+    let mut met: Option<usize> = None;
+    let mut next_q = Vec::new();
+    for &idx in q.iter() {
+        for next_idx in neighbors(idx) {
+            if let Some(&other_dist) = other_seen.get(&next_idx) {
+                met = Some(met.map_or(level + other_dist, |d| d.min(level + other_dist)));
+            }
+            if let Entry::Vacant(entry) = seen.entry(next_idx) {
+                entry.insert(level);
+                next_q.push(next_idx);
+            }
+        }
+    }
+    *q = next_q;
+    met
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Rng;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_bidirectional_shortest() {
+        // 随机稀疏图，与逐个起点 BFS 的结果对照
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let n = 60;
+        for i in 0..n {
+            graph.add_node(i, ());
+        }
+        let mut rng = Rng::new(7);
+        for _ in 0..120 {
+            graph.add_edge(rng.gen_range(n), rng.gen_range(n));
+        }
+        let hops = graph.all_pairs_bfs();
+        for src_idx in 0..n {
+            for dst_idx in 0..n {
+                let d = hops.dist[src_idx][dst_idx];
+                for max_level in [0, 1, 2, 3, 5, 100] {
+                    let expected = (d.is_finite() && (d as usize) < max_level).then_some(d as usize);
+                    assert_eq!(graph.get_shortest(src_idx, dst_idx, max_level), expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+/*
+全源最短路，结果为稠密矩阵 DistanceMatrix，按节点 index 取值：dist[src_idx][dst_idx]
+- all_pairs_bfs：不带权，距离为跳数，每个节点做一次 BFS，O(nm)
+- floyd_warshall：带权，允许负权，O(n³)，适合节点少、边稠密的图
+- johnson：带权，允许负权。先用 bellman_ford 的松弛算出势能 h，把边权改为 w + h[u] - h[v]（非负），
+  再对每个节点跑 dijkstra，O(nm log m)，适合稀疏图
+- 存在负环时，floyd_warshall 和 johnson 返回 Error::NegativeCycle
+- 由矩阵可以得到 eccentricity（离心率）、diameter（直径）、radius（半径）、center（中心）
+  有向图中离心率按出方向计算；存在不可达的节点时，离心率为 f64::INFINITY
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+*/
+
+use std::collections::VecDeque;
+use crate::error::Result;
+use super::adjacency::Adjacency;
+use super::bellman_ford::{bellman_ford, relax_rounds};
+use super::dijkstra::dijkstra;
+
+
+pub struct DistanceMatrix {
+    // dist[src_idx][dst_idx]：最短距离，不可达为 f64::INFINITY，自身为 0
+    pub dist: Vec<Vec<f64>>,
+    // prev[src_idx][dst_idx]：从 src_idx 到 dst_idx 的最短路径上，dst_idx 的前一个节点
+    pub prev: Vec<Vec<Option<usize>>>,
+}
+
+impl DistanceMatrix {
+    // 从 src_idx 到 dst_idx 的最短路径，包含起点和终点。不可达时返回 None
+    pub fn path(&self, src_idx: usize, dst_idx: usize) -> Option<Vec<usize>> {
+        if self.dist[src_idx][dst_idx].is_infinite() {
+            return None;
+        }
+        let mut path = vec![dst_idx];
+        while *path.last().unwrap() != src_idx {
+            path.push(self.prev[src_idx][*path.last().unwrap()]?);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // 每个节点到其它节点的最大距离
+    pub fn eccentricity(&self) -> Vec<f64> {
+        self.dist.iter()
+            .map(|row| row.iter().copied().fold(0.0, f64::max))
+            .collect()
+    }
+
+    // 最大的离心率。空图为 0
+    pub fn diameter(&self) -> f64 {
+        self.eccentricity().into_iter().fold(0.0, f64::max)
+    }
+
+    // 最小的离心率。空图为 0
+    pub fn radius(&self) -> f64 {
+        self.eccentricity().into_iter().reduce(f64::min).unwrap_or(0.0)
+    }
+
+    // 离心率等于半径的节点
+    pub fn center(&self) -> Vec<usize> {
+        let eccentricity = self.eccentricity();
+        let radius = self.radius();
+        (0..eccentricity.len()).filter(|&idx| eccentricity[idx] == radius).collect()
+    }
+}
+
+
+// Adjacency::all_pairs_bfs 的实现
+pub(super) fn all_pairs_bfs<G: Adjacency + ?Sized>(graph: &G) -> DistanceMatrix {
+    let n = graph.node_count();
+    let mut dist = vec![vec![f64::INFINITY; n]; n];
+    let mut prev = vec![vec![None; n]; n];
+    for src_idx in 0..n {
+        let (dist_row, prev_row) = (&mut dist[src_idx], &mut prev[src_idx]);
+        dist_row[src_idx] = 0.0;
+        let mut q = VecDeque::from([src_idx]);
+        while let Some(idx) = q.pop_front() {
+            for next_idx in graph.successors(idx) {
+                if dist_row[next_idx].is_infinite() {
+                    dist_row[next_idx] = dist_row[idx] + 1.0;
+                    prev_row[next_idx] = Some(idx);
+                    q.push_back(next_idx);
+                }
+            }
+        }
+    }
+    DistanceMatrix { dist, prev }
+}
+
+
+// Adjacency::floyd_warshall 的实现
+pub(super) fn floyd_warshall<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64) -> Result<DistanceMatrix> {
+    let n = graph.node_count();
+    let mut dist = vec![vec![f64::INFINITY; n]; n];
+    let mut prev = vec![vec![None; n]; n];
+    for (idx, row) in dist.iter_mut().enumerate() {
+        row[idx] = 0.0;
+    }
+    for (src_idx, dst_idx) in graph.get_all_edges() {
+        let w = weight(src_idx, dst_idx);
+        if w < dist[src_idx][dst_idx] {
+            dist[src_idx][dst_idx] = w;
+            prev[src_idx][dst_idx] = Some(src_idx);
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            if dist[i][k].is_infinite() {
+                continue;
+            }
+            for j in 0..n {
+                let new_dist = dist[i][k] + dist[k][j];
+                if new_dist < dist[i][j] {
+                    dist[i][j] = new_dist;
+                    prev[i][j] = prev[k][j];
+                }
+            }
+        }
+    }
+
+    // 某个节点到自身的距离为负，说明它在负环上。从它出发跑一次 bellman_ford，取出完整的环
+    if let Some(idx) = (0..n).find(|&idx| dist[idx][idx] < 0.0) {
+        bellman_ford(graph, idx, &weight)?;
+    }
+    Ok(DistanceMatrix { dist, prev })
+}
+
+// Adjacency::johnson 的实现
+pub(super) fn johnson<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64) -> Result<DistanceMatrix> {
+    let n = graph.node_count();
+    let mut h = vec![0.0; n];
+    relax_rounds(graph, &mut h, &weight)?;
+
+    // 改写后的边权理论上非负，浮点误差可能带来极小的负数，截断到 0
+    let reweighted = |src_idx, dst_idx| (weight(src_idx, dst_idx) + h[src_idx] - h[dst_idx]).max(0.0);
+    let mut dist = Vec::with_capacity(n);
+    let mut prev = Vec::with_capacity(n);
+    for src_idx in 0..n {
+        let paths = dijkstra(graph, src_idx, reweighted)?;
+        dist.push(paths.dist.iter().enumerate()
+            .map(|(dst_idx, d)| d - h[src_idx] + h[dst_idx])
+            .collect());
+        prev.push(paths.prev);
+    }
+    Ok(DistanceMatrix { dist, prev })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_all_pairs() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("A", "B", 3.0), ("B", "A", 1.0), ("B", "C", -2.0), ("C", "D", 2.0), ("A", "D", 4.0),
+            ("D", "B", 1.0), ("D", "A", 3.0)];
+        let mut weights = HashMap::new();
+        for (src_name, dst_name, w) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+            weights.insert((*graph.get_idx_by_name(src_name).unwrap(), *graph.get_idx_by_name(dst_name).unwrap()), w);
+        }
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+        let [a, b, c, d] = ["A", "B", "C", "D"].map(|name| *graph.get_idx_by_name(name).unwrap());
+
+        // 跳数：所有 get_shortest 的结果一次算完
+        let hops = graph.all_pairs_bfs();
+        for src_idx in 0..4 {
+            for dst_idx in 0..4 {
+                assert_eq!(Some(hops.dist[src_idx][dst_idx] as usize), graph.get_shortest(src_idx, dst_idx, 10));
+            }
+        }
+        assert_eq!(hops.path(c, a), Some(vec![c, d, a]));
+        assert_eq!(hops.eccentricity(), vec![2.0, 2.0, 2.0, 2.0]);
+
+        let fw = graph.floyd_warshall(weight).unwrap();
+        let johnson = graph.johnson(weight).unwrap();
+        for matrix in [&fw, &johnson] {
+            assert_eq!(matrix.dist[a][d], 3.0);
+            assert_eq!(matrix.path(a, d), Some(vec![a, b, c, d]));
+            assert_eq!(matrix.dist[d][c], -1.0);
+            assert_eq!(matrix.eccentricity(), vec![3.0, 1.0, 4.0, 2.0]);
+            assert_eq!(matrix.diameter(), 4.0);
+            assert_eq!(matrix.radius(), 1.0);
+            assert_eq!(matrix.center(), vec![b]);
+        }
+
+        // 新增孤立节点后，离心率和直径变为无穷大
+        graph.add_node("E", ());
+        let hops = graph.all_pairs_bfs();
+        assert_eq!(hops.diameter(), f64::INFINITY);
+        assert_eq!(hops.path(a, 4), None);
+
+        // 冻结后结果相同
+        let expected = graph.johnson(weight).unwrap();
+        let frozen = arena_list.freeze();
+        for matrix in [frozen.floyd_warshall(weight).unwrap(), frozen.johnson(weight).unwrap()] {
+            assert_eq!(matrix.dist, expected.dist);
+            assert_eq!(matrix.path(a, d), Some(vec![a, b, c, d]));
+        }
+    }
+}
@@ -0,0 +1,170 @@
+/*
+A* 搜索，沿 next_idx 寻找代价最小的路径
+- cost(src_idx, dst_idx)：边的代价，需非负
+- heuristic(idx, &data)：从该节点到终点的估计代价。不高估实际代价时，结果是最优的
+- max_expansion：最多展开的节点个数，类似 get_shortest 的 max_level，超出后返回 None，保证病态查询能结束
+返回 (路径上的节点 index，总代价)，路径包含起点和终点
+heuristic 要读节点数据，不写在 Adjacency 上；Graph 和 FrozenGraph 各有一个 astar，共用同一份实现
+*/
+
+use std::collections::BinaryHeap;
+use crate::error::Result;
+use super::adjacency::{self, Adjacency};
+use super::scored::MinScored;
+use super::{FrozenGraph, Graph};
+
+
+// 只依赖拓扑结构的部分，heuristic 只接收节点 index；Graph 和 FrozenGraph 的 astar 把节点数据传给调用方的 heuristic
+fn astar<G: Adjacency + ?Sized>(
+    graph: &G,
+    src_idx: usize,
+    dst_idx: usize,
+    cost: impl Fn(usize, usize) -> f64,
+    heuristic: impl Fn(usize) -> f64,
+    max_expansion: usize,
+) -> Result<Option<(Vec<usize>, f64)>> {
+    adjacency::check_idx(graph, src_idx)?;
+    adjacency::check_idx(graph, dst_idx)?;
+    let n = graph.node_count();
+
+    let mut g_score = vec![f64::INFINITY; n];
+    let mut came_from = vec![usize::MAX; n];
+    // 堆中存放 (g + h, (g, idx))。同一个节点可能有多个过时的记录，弹出时跳过
+    let mut heap = BinaryHeap::new();
+    g_score[src_idx] = 0.0;
+    heap.push(MinScored(heuristic(src_idx), (0.0, src_idx)));
+
+    let mut expansion = 0;
+    while let Some(MinScored(_, (g, idx))) = heap.pop() {
+        if g > g_score[idx] {
+            continue;
+        }
+        if idx == dst_idx {
+            let mut path = vec![dst_idx];
+            while *path.last().unwrap() != src_idx {
+                path.push(came_from[*path.last().unwrap()]);
+            }
+            path.reverse();
+            return Ok(Some((path, g)));
+        }
+        if expansion >= max_expansion {
+            return Ok(None);
+        }
+        expansion += 1;
+
+        for next_idx in graph.successors(idx) {
+            let next_g = g + cost(idx, next_idx);
+            if next_g < g_score[next_idx] {
+                g_score[next_idx] = next_g;
+                came_from[next_idx] = idx;
+                heap.push(MinScored(next_g + heuristic(next_idx), (next_g, next_idx)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+
+impl<'a, K, T> Graph<'a, K, T> {
+    pub fn astar(
+        &self,
+        src_idx: usize,
+        dst_idx: usize,
+        cost: impl Fn(usize, usize) -> f64,
+        heuristic: impl Fn(usize, &T) -> f64,
+        max_expansion: usize,
+    ) -> Result<Option<(Vec<usize>, f64)>> {
+        let nodes = &self.owner.nodes;
+        astar(self, src_idx, dst_idx, cost, |idx| heuristic(idx, &nodes[idx].data), max_expansion)
+    }
+}
+
+impl<K, T> FrozenGraph<K, T> {
+    pub fn astar(
+        &self,
+        src_idx: usize,
+        dst_idx: usize,
+        cost: impl Fn(usize, usize) -> f64,
+        heuristic: impl Fn(usize, &T) -> f64,
+        max_expansion: usize,
+    ) -> Result<Option<(Vec<usize>, f64)>> {
+        astar(self, src_idx, dst_idx, cost, |idx| heuristic(idx, self.get_data_by_idx(idx)), max_expansion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{ArenaList, Graph};
+
+    #[test]
+    fn test_astar() {
+        // 网格上的节点，data 为坐标，边代价为欧氏距离，启发函数为到终点的欧氏距离
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let n = 10;
+        for x in 0..n {
+            for y in 0..n {
+                graph.add_node((x, y), (x as f64, y as f64));
+            }
+        }
+        for x in 0..n {
+            for y in 0..n {
+                // 中间有一堵墙，只在 y = 9 处开口
+                if x == 4 && y != 9 {
+                    continue;
+                }
+                let idx = *graph.get_idx_by_name(&(x, y)).unwrap();
+                for (dx, dy) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if (0..n).contains(&nx) && (0..n).contains(&ny) && !(nx == 4 && ny != 9) {
+                        let next_idx = *graph.get_idx_by_name(&(nx, ny)).unwrap();
+                        graph.add_edge(idx, next_idx);
+                    }
+                }
+            }
+        }
+
+        let src_idx = *graph.get_idx_by_name(&(0, 0)).unwrap();
+        let dst_idx = *graph.get_idx_by_name(&(9, 0)).unwrap();
+        let (dst_x, dst_y) = graph.get_node_by_idx(dst_idx).data;
+        let cost = |_, _| 1.0;
+        let heuristic = |_, &(x, y): &(f64, f64)| ((x - dst_x).powi(2) + (y - dst_y).powi(2)).sqrt();
+
+        let (path, total_cost) = graph.astar(src_idx, dst_idx, cost, heuristic, 1000).unwrap().unwrap();
+        assert_eq!(total_cost, 27.0);
+        assert_eq!(path.len(), 28);
+        assert_eq!(path[0], src_idx);
+        assert!(path.contains(graph.get_idx_by_name(&(4, 9)).unwrap()));
+
+        // 展开预算不够时返回 None
+        assert_eq!(graph.astar(src_idx, dst_idx, cost, heuristic, 10).unwrap(), None);
+        assert!(graph.astar(src_idx, 1000, cost, heuristic, 10).is_err());
+
+        // 冻结后代价相同（等长的路径可能不同）
+        let frozen = arena_list.freeze();
+        let (frozen_path, frozen_cost) = frozen.astar(src_idx, dst_idx, cost, heuristic, 1000).unwrap().unwrap();
+        assert_eq!((frozen_path.len(), frozen_cost), (path.len(), total_cost));
+        assert_eq!(frozen.astar(src_idx, dst_idx, cost, heuristic, 10).unwrap(), None);
+    }
+
+    #[test]
+    fn test_astar_edge_cases() {
+        // A -> A（自环）、A -> B，C 孤立
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("A", (), "A", ());
+        graph.add_node_and_edge("A", (), "B", ());
+        graph.add_node("C", ());
+        let [a, b, c] = ["A", "B", "C"].map(|name| *graph.get_idx_by_name(name).unwrap());
+        let heuristic = |_, _: &()| 0.0;
+
+        // 起点就是终点：不需要展开，预算为 0 也能找到
+        assert_eq!(graph.astar(a, a, |_, _| 1.0, heuristic, 0).unwrap(), Some((vec![a], 0.0)));
+        assert_eq!(graph.astar(a, b, |_, _| 1.0, heuristic, 0).unwrap(), None);
+        assert_eq!(graph.astar(a, b, |_, _| 1.0, heuristic, 1).unwrap(), Some((vec![a, b], 1.0)));
+        // 不可达
+        assert_eq!(graph.astar(a, c, |_, _| 1.0, heuristic, 100).unwrap(), None);
+        assert_eq!(graph.astar(b, a, |_, _| 1.0, heuristic, 100).unwrap(), None);
+        assert!(graph.astar(100, a, |_, _| 1.0, heuristic, 100).is_err());
+    }
+}
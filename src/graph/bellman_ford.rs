@@ -0,0 +1,249 @@
+/*
+单源最短路，边权可以为负
+- bellman_ford：反复松弛所有边，某一轮没有更新时提前结束，O(nm)
+- spfa：只把距离变短的节点放入队列，一般比 bellman_ford 快很多，最坏同样 O(nm)
+- 从起点可达的负环会让最短路无意义，此时返回 Error::NegativeCycle，存放环上的节点 index
+  （按边的方向排列，最后一个节点指向第一个节点），环不会为空
+  用 Graph::get_cycle_names(&err) 转为节点名
+- 不可达的负环不影响结果
+- 距离按 EPS 比较，变短不超过 EPS 的不算更新，权重为 0 的环上的浮点误差不会被当成负环
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+*/
+
+use std::collections::VecDeque;
+use crate::error::{Error, Result};
+use super::adjacency::{self, Adjacency};
+
+
+const EPS: f64 = 1e-9;
+
+pub struct ShortestPaths {
+    pub src_idx: usize,
+    // 从起点出发到各节点的最短距离，不可达为 f64::INFINITY
+    pub dist: Vec<f64>,
+    // 最短路径上的前一个节点，起点和不可达的节点为 None
+    pub prev: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    // 从起点到 dst_idx 的最短路径，包含起点和终点。不可达时返回 None
+    pub fn path_to(&self, dst_idx: usize) -> Option<Vec<usize>> {
+        if self.dist[dst_idx].is_infinite() {
+            return None;
+        }
+        let mut path = vec![dst_idx];
+        while let Some(prev_idx) = self.prev[*path.last().unwrap()] {
+            path.push(prev_idx);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+
+// 沿 prev 往回走，找到其中的环。先走 n 步，还没走到头说明已经落在环上，再走一圈把环取出来
+// 中途遇到没有 prev 的节点（链的起点）时返回 None
+pub(super) fn find_cycle(prev: &[Option<usize>], start: usize) -> Option<Vec<usize>> {
+    let mut curr = start;
+    for _ in 0..prev.len() {
+        curr = prev[curr]?;
+    }
+    let mut cycle = vec![curr];
+    let mut node = prev[curr]?;
+    while node != curr {
+        cycle.push(node);
+        node = prev[node]?;
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+
+// Adjacency::bellman_ford 的实现
+pub(super) fn bellman_ford<G: Adjacency + ?Sized>(graph: &G, src_idx: usize, weight: impl Fn(usize, usize) -> f64) -> Result<ShortestPaths> {
+    adjacency::check_idx(graph, src_idx)?;
+    let mut dist = vec![f64::INFINITY; graph.node_count()];
+    dist[src_idx] = 0.0;
+    let prev = relax_rounds(graph, &mut dist, weight)?;
+    Ok(ShortestPaths { src_idx, dist, prev })
+}
+
+// 以 dist 为初始距离反复松弛所有边，直到没有更新。
+// dist 全为 0 时，相当于从一个连向所有节点的虚拟起点出发，johnson 用它计算势能
+pub(super) fn relax_rounds<G: Adjacency + ?Sized>(graph: &G, dist: &mut [f64], weight: impl Fn(usize, usize) -> f64) -> Result<Vec<Option<usize>>> {
+    let n = graph.node_count();
+    let mut prev = vec![None; n];
+
+    // 最短路径最多 n - 1 条边（算上虚拟起点最多 n 条），第 n + 1 轮仍有更新，说明有负环
+    for round in 0..=n {
+        let mut updated = None;
+        for idx in 0..n {
+            if dist[idx].is_infinite() {
+                continue;
+            }
+            for next_idx in graph.successors(idx) {
+                let new_dist = dist[idx] + weight(idx, next_idx);
+                if new_dist < dist[next_idx] - EPS {
+                    dist[next_idx] = new_dist;
+                    prev[next_idx] = Some(idx);
+                    updated = Some(next_idx);
+                }
+            }
+        }
+        match updated {
+            None => break,
+            Some(idx) if round == n => {
+                // 第 n + 1 轮还被更新的节点，沿 prev 往回走 n 步会落在负环上
+                // 走不回来时再检查其它节点，prev 中都没有环说明只是浮点误差，按已经收敛处理
+                if let Some(cycle) = find_cycle(&prev, idx).or_else(|| (0..n).find_map(|idx| find_cycle(&prev, idx))) {
+                    return Err(Error::NegativeCycle(cycle));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(prev)
+}
+
+// Adjacency::spfa 的实现
+pub(super) fn spfa<G: Adjacency + ?Sized>(graph: &G, src_idx: usize, weight: impl Fn(usize, usize) -> f64) -> Result<ShortestPaths> {
+    adjacency::check_idx(graph, src_idx)?;
+    let n = graph.node_count();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut prev = vec![None; n];
+    // 当前最短路径包含的边数，达到 n 说明路径上有环，而且是负环
+    let mut path_len = vec![0; n];
+    let mut in_queue = vec![false; n];
+    let mut q = VecDeque::from([src_idx]);
+    dist[src_idx] = 0.0;
+    in_queue[src_idx] = true;
+
+    while let Some(idx) = q.pop_front() {
+        in_queue[idx] = false;
+        for next_idx in graph.successors(idx) {
+            let new_dist = dist[idx] + weight(idx, next_idx);
+            if new_dist < dist[next_idx] - EPS {
+                dist[next_idx] = new_dist;
+                prev[next_idx] = Some(idx);
+                path_len[next_idx] = path_len[idx] + 1;
+                if path_len[next_idx] >= n {
+                    // prev 在队列处理过程中还会变化，不一定能直接取出环，取不出时交给 bellman_ford
+                    return match find_cycle(&prev, next_idx) {
+                        Some(cycle) => Err(Error::NegativeCycle(cycle)),
+                        None => bellman_ford(graph, src_idx, &weight),
+                    };
+                }
+                if !in_queue[next_idx] {
+                    in_queue[next_idx] = true;
+                    q.push_back(next_idx);
+                }
+            }
+        }
+    }
+    Ok(ShortestPaths { src_idx, dist, prev })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+    use crate::{Error, Rng};
+
+    // 按节点名加边，返回 {(src_idx, dst_idx): weight}
+    fn add_weighted_edges(graph: &mut Graph<'_, &'static str, ()>, edges: &[(&'static str, &'static str, f64)]) -> HashMap<(usize, usize), f64> {
+        let mut weights = HashMap::new();
+        for &(src_name, dst_name, w) in edges {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+            weights.insert((*graph.get_idx_by_name(src_name).unwrap(), *graph.get_idx_by_name(dst_name).unwrap()), w);
+        }
+        weights
+    }
+
+    #[test]
+    fn test_negative_edges() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let weights = add_weighted_edges(&mut graph, &[
+            ("S", "A", 4.0), ("S", "B", 5.0), ("A", "C", 3.0), ("B", "A", -3.0), ("C", "D", 2.0), ("B", "D", 7.0), ("X", "S", 1.0)]);
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+        for paths in [graph.bellman_ford(0, weight).unwrap(), graph.spfa(0, weight).unwrap()] {
+            let d_idx = *graph.get_idx_by_name("D").unwrap();
+            assert_eq!(paths.dist[d_idx], 7.0);
+            let path = paths.path_to(d_idx).unwrap();
+            assert_eq!(graph.get_names_by_idxes(&path), vec![Some(&"S"), Some(&"B"), Some(&"A"), Some(&"C"), Some(&"D")]);
+            let x_idx = *graph.get_idx_by_name("X").unwrap();
+            assert_eq!(paths.path_to(x_idx), None);
+        }
+
+        // 冻结后结果相同
+        let expected = graph.bellman_ford(0, weight).unwrap();
+        let frozen = arena_list.freeze();
+        for paths in [frozen.bellman_ford(0, weight).unwrap(), frozen.spfa(0, weight).unwrap()] {
+            assert_eq!(paths.dist, expected.dist);
+            assert_eq!(paths.prev, expected.prev);
+        }
+        assert!(matches!(frozen.spfa(9, weight), Err(Error::UnknownNode(9))));
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let weights = add_weighted_edges(&mut graph, &[
+            ("S", "A", 1.0), ("A", "B", 1.0), ("B", "C", -2.0), ("C", "A", -1.0), ("C", "D", 1.0)]);
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+        for res in [graph.bellman_ford(0, weight), graph.spfa(0, weight)] {
+            let err = res.err().unwrap();
+            assert!(matches!(err, Error::NegativeCycle(_)));
+            // 环的起点不固定，转到以 A 开头再比较
+            let mut names = graph.get_cycle_names(&err).unwrap();
+            let start = names.iter().position(|name| name == &Some(&"A")).unwrap();
+            names.rotate_left(start);
+            assert_eq!(names, vec![Some(&"A"), Some(&"B"), Some(&"C")]);
+        }
+        // 从 D 出发，负环不可达
+        let d_idx = *graph.get_idx_by_name("D").unwrap();
+        assert!(graph.bellman_ford(d_idx, weight).is_ok());
+        assert!(graph.spfa(d_idx, weight).is_ok());
+
+        // 负权自环：环只有一个节点，但不为空
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let weights = add_weighted_edges(&mut graph, &[("S", "A", 1.0), ("A", "A", -1.0)]);
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+        for res in [graph.bellman_ford(0, weight), graph.spfa(0, weight)] {
+            assert_eq!(graph.get_cycle_names(&res.err().unwrap()), Some(vec![Some(&"A")]));
+        }
+        assert_eq!(graph.get_cycle_names(&Error::UnknownName), None);
+    }
+
+    #[test]
+    fn test_zero_weight_cycles() {
+        // 边权为 p[dst] - p[src]，所有环的权重都是 0，只有浮点误差，不能报负环，也不能 panic
+        let mut rng = Rng::new(2);
+        for _ in 0..300 {
+            let n = 2 + rng.gen_range(10);
+            let mut arena_list = ArenaList::new();
+            let mut graph = Graph::new(&mut arena_list);
+            for i in 0..n {
+                graph.add_node(i, ());
+            }
+            let potential: Vec<f64> = (0..n).map(|_| rng.gen_range(1000) as f64 / 7.0).collect();
+            for _ in 0..rng.gen_range(4 * n) {
+                let (src_idx, dst_idx) = (rng.gen_range(n), rng.gen_range(n));
+                graph.add_edge(src_idx, dst_idx);
+                graph.add_edge(dst_idx, src_idx);
+            }
+            let weight = |src_idx: usize, dst_idx: usize| potential[dst_idx] - potential[src_idx];
+            for paths in [graph.bellman_ford(0, weight).unwrap(), graph.spfa(0, weight).unwrap()] {
+                for idx in 0..n {
+                    if paths.dist[idx].is_finite() {
+                        assert!((paths.dist[idx] - weight(0, idx)).abs() < 1e-6);
+                        assert_eq!(paths.path_to(idx).unwrap()[0], 0);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+/*
+二分图，把有向边看作无向边
+- bipartition：BFS 二染色，成功时返回每个节点的颜色，失败时返回一个奇数长度的环作为证据
+  自环本身就是长度为 1 的奇环
+- max_matching：Hopcroft-Karp 最大匹配，O(m√n)。每轮 BFS 分层，记下最短增广路的长度，
+  再沿层次图找一批不相交的最短增广路：只在这一层结束于未匹配的右侧节点
+- min_cost_assignment：匈牙利算法（带势能的 O(n³) 版本），在匹配数最多的前提下使总代价最小
+  代价由 cost(src_idx, dst_idx) 给出，没有边的两个节点不能匹配；求最大收益时传入相反数
+- 匹配以边的形式返回 (src_idx, dst_idx)，方向与原图中的边一致，可以用 get_names_by_idxes 转为节点名
+- 不是二分图时，max_matching 和 min_cost_assignment 返回 Error::NotBipartite
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+*/
+
+use std::collections::VecDeque;
+use crate::error::{Error, Result};
+use super::adjacency::Adjacency;
+
+
+pub enum Bipartition {
+    // 每个节点的颜色，按节点 index 排列。每个连通分量中 index 最小的节点为 false
+    Coloring(Vec<bool>),
+    // 一个奇数长度的环，沿着环排列（最后一个节点与第一个节点相连）
+    OddCycle(Vec<usize>),
+}
+
+
+// 无向视角下的邻居：successors 和 predecessors 的并集，从小到大排列。有自环时包含节点自身
+pub(super) fn undirected_neighbors<G: Adjacency + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    (0..graph.node_count())
+        .map(|idx| {
+            let mut neighbors: Vec<usize> = graph.successors(idx).chain(graph.predecessors(idx)).collect();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            neighbors
+        })
+        .collect()
+}
+
+// Adjacency::bipartition、max_matching、min_cost_assignment 的实现
+pub(super) fn bipartition<G: Adjacency + ?Sized>(graph: &G) -> Bipartition {
+    let neighbors = undirected_neighbors(graph);
+    let n = neighbors.len();
+    let mut color = vec![false; n];
+    let mut depth = vec![usize::MAX; n];
+    let mut parent = vec![usize::MAX; n];
+    for root in 0..n {
+        if depth[root] != usize::MAX {
+            continue;
+        }
+        depth[root] = 0;
+        let mut q = VecDeque::from([root]);
+        while let Some(idx) = q.pop_front() {
+            for &next_idx in &neighbors[idx] {
+                if depth[next_idx] == usize::MAX {
+                    depth[next_idx] = depth[idx] + 1;
+                    color[next_idx] = !color[idx];
+                    parent[next_idx] = idx;
+                    q.push_back(next_idx);
+                } else if color[next_idx] == color[idx] {
+                    return Bipartition::OddCycle(odd_cycle(&parent, &depth, idx, next_idx));
+                }
+            }
+        }
+    }
+    Bipartition::Coloring(color)
+}
+
+// 二染色，不是二分图时返回 Error::NotBipartite
+fn try_coloring<G: Adjacency + ?Sized>(graph: &G) -> Result<Vec<bool>> {
+    match bipartition(graph) {
+        Bipartition::Coloring(color) => Ok(color),
+        Bipartition::OddCycle(cycle) => Err(Error::NotBipartite(cycle)),
+    }
+}
+
+// 匹配的两个节点之间的边，按原图中的方向给出
+fn matched_edge<G: Adjacency + ?Sized>(graph: &G, idx1: usize, idx2: usize) -> (usize, usize) {
+    if graph.has_edge(idx1, idx2) { (idx1, idx2) } else { (idx2, idx1) }
+}
+
+pub(super) fn max_matching<G: Adjacency + ?Sized>(graph: &G) -> Result<Vec<(usize, usize)>> {
+    let color = try_coloring(graph)?;
+    let neighbors = undirected_neighbors(graph);
+    let n = neighbors.len();
+    let left: Vec<usize> = (0..n).filter(|&idx| !color[idx]).collect();
+    let mut mate: Vec<Option<usize>> = vec![None; n];
+
+    loop {
+        // 从所有未匹配的左侧节点出发 BFS，给左侧节点分层
+        // limit：最早遇到未匹配右侧节点的层，即最短增广路的长度，更深的层不再展开
+        let mut dist = vec![usize::MAX; n];
+        let mut q: VecDeque<usize> = left.iter().copied().filter(|&idx| mate[idx].is_none()).collect();
+        for &idx in &q {
+            dist[idx] = 0;
+        }
+        let mut limit = usize::MAX;
+        while let Some(idx) = q.pop_front() {
+            if dist[idx] > limit {
+                break;
+            }
+            for &right_idx in &neighbors[idx] {
+                match mate[right_idx] {
+                    None => limit = dist[idx],
+                    Some(left_idx) if dist[left_idx] == usize::MAX && dist[idx] < limit => {
+                        dist[left_idx] = dist[idx] + 1;
+                        q.push_back(left_idx);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if limit == usize::MAX {
+            break;
+        }
+
+        // 沿层次图找增广路，用显式的栈代替递归
+        let mut it = vec![0; n];
+        for &root in &left {
+            if mate[root].is_some() {
+                continue;
+            }
+            // stack 中是路径上的左侧节点，chosen[i] 是 stack[i] 选中的右侧节点
+            let mut stack = vec![root];
+            let mut chosen: Vec<usize> = Vec::new();
+            while let Some(&idx) = stack.last() {
+                let next = neighbors[idx][it[idx]..].iter().position(|&right_idx| match mate[right_idx] {
+                    None => dist[idx] == limit,
+                    Some(left_idx) => dist[idx] < limit && dist[left_idx] == dist[idx] + 1,
+                });
+                match next {
+                    Some(offset) => {
+                        it[idx] += offset;
+                        let right_idx = neighbors[idx][it[idx]];
+                        chosen.push(right_idx);
+                        match mate[right_idx] {
+                            Some(left_idx) => stack.push(left_idx),
+                            None => {
+                                // 找到增广路，沿路翻转匹配
+                                for (&left_idx, &right_idx) in stack.iter().zip(&chosen) {
+                                    mate[left_idx] = Some(right_idx);
+                                    mate[right_idx] = Some(left_idx);
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    None => {
+                        // 死胡同，本轮不再经过它
+                        dist[idx] = usize::MAX;
+                        stack.pop();
+                        if chosen.pop().is_some() {
+                            it[*stack.last().unwrap()] += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(left.iter()
+        .filter_map(|&idx| mate[idx].map(|right_idx| matched_edge(graph, idx, right_idx)))
+        .collect())
+}
+
+// 返回 (匹配的边, 总代价)
+pub(super) fn min_cost_assignment<G: Adjacency + ?Sized>(graph: &G, cost: impl Fn(usize, usize) -> f64) -> Result<(Vec<(usize, usize)>, f64)> {
+    let color = try_coloring(graph)?;
+    let neighbors = undirected_neighbors(graph);
+    let n = neighbors.len();
+    let mut rows: Vec<usize> = (0..n).filter(|&idx| !color[idx]).collect();
+    let mut cols: Vec<usize> = (0..n).filter(|&idx| color[idx]).collect();
+    // 算法要求行数不多于列数
+    if rows.len() > cols.len() {
+        std::mem::swap(&mut rows, &mut cols);
+    }
+    let mut col_pos = vec![usize::MAX; n];
+    for (j, &idx) in cols.iter().enumerate() {
+        col_pos[idx] = j;
+    }
+
+    // 代价矩阵，没有边的位置填一个足够大的数 big：先保证匹配数最多，再最小化代价
+    let mut a = vec![vec![None; cols.len()]; rows.len()];
+    let mut big = 1.0;
+    for (i, &row_idx) in rows.iter().enumerate() {
+        for &idx in &neighbors[row_idx] {
+            let (src_idx, dst_idx) = matched_edge(graph, row_idx, idx);
+            let c = cost(src_idx, dst_idx);
+            big += c.abs();
+            let cell: &mut Option<f64> = &mut a[i][col_pos[idx]];
+            *cell = Some(cell.map_or(c, |old| old.min(c)));
+        }
+    }
+    let big = big * (rows.len() + 1) as f64;
+    let at = |i: usize, j: usize| a[i - 1][j - 1].unwrap_or(big);
+
+    // 下标从 1 开始，第 0 列是虚拟列。p[j]：第 j 列匹配的行
+    let (r, c) = (rows.len(), cols.len());
+    let mut u = vec![0.0; r + 1];
+    let mut v = vec![0.0; c + 1];
+    let mut p = vec![0; c + 1];
+    let mut way = vec![0; c + 1];
+    for i in 1..=r {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![f64::INFINITY; c + 1];
+        let mut used = vec![false; c + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=c {
+                if !used[j] {
+                    let cur = at(i0, j) - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=c {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut matching = Vec::new();
+    let mut total_cost = 0.0;
+    for j in 1..=c {
+        if let Some(w) = (p[j] != 0).then(|| a[p[j] - 1][j - 1]).flatten() {
+            matching.push(matched_edge(graph, rows[p[j] - 1], cols[j - 1]));
+            total_cost += w;
+        }
+    }
+    matching.sort_unstable();
+    Ok((matching, total_cost))
+}
+
+
+// BFS 树上 idx1、idx2 颜色相同且相邻：两者分别往上走到最近公共祖先，与边 idx1-idx2 组成奇环
+fn odd_cycle(parent: &[usize], depth: &[usize], idx1: usize, idx2: usize) -> Vec<usize> {
+    let (mut a, mut b) = (idx1, idx2);
+    let mut path_a = vec![a];
+    let mut path_b = vec![b];
+    while a != b {
+        if depth[a] >= depth[b] {
+            a = parent[a];
+            path_a.push(a);
+        } else {
+            b = parent[b];
+            path_b.push(b);
+        }
+    }
+    // 两条路径都以公共祖先结尾，只保留一份
+    path_b.pop();
+    path_b.reverse();
+    path_a.reverse();
+    path_a.extend(path_b);
+    path_a
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use crate::graph::{Adjacency, ArenaList, Bipartition, Graph};
+    use crate::{Error, Rng};
+
+    #[test]
+    fn test_bipartition() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 工人 -> 任务
+        let vec1 = vec![("W1", "J1"), ("W1", "J2"), ("W2", "J1"), ("W3", "J2"), ("W3", "J3"), ("W4", "J3")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let color = match graph.bipartition() {
+            Bipartition::Coloring(color) => color,
+            Bipartition::OddCycle(_) => panic!("应当是二分图"),
+        };
+        for (src_idx, dst_idx) in graph.get_all_edges() {
+            assert_ne!(color[src_idx], color[dst_idx]);
+        }
+        let matching = graph.max_matching().unwrap();
+        assert_eq!(matching.len(), 3);
+        for &(src_idx, dst_idx) in &matching {
+            assert!(graph.get_node_by_idx(src_idx).next_idx.contains(&dst_idx));
+        }
+
+        // J1 -> J2 构成奇环 W1-J1-J2
+        graph.add_edge(*graph.get_idx_by_name("J1").unwrap(), *graph.get_idx_by_name("J2").unwrap());
+        let cycle = match graph.bipartition() {
+            Bipartition::OddCycle(cycle) => cycle,
+            Bipartition::Coloring(_) => panic!("应当找到奇环"),
+        };
+        assert_eq!(cycle.len(), 3);
+        for i in 0..cycle.len() {
+            let (idx1, idx2) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            let node = graph.get_node_by_idx(idx1);
+            assert!(node.next_idx.contains(&idx2) || node.prev_idx.contains(&idx2));
+        }
+        assert!(matches!(graph.max_matching(), Err(Error::NotBipartite(_))));
+    }
+
+    #[test]
+    fn test_min_cost_assignment() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // W3 只能做 J1
+        let vec1 = vec![
+            ("W1", "J1", 9.0), ("W1", "J2", 2.0), ("W1", "J3", 7.0),
+            ("W2", "J1", 6.0), ("W2", "J2", 4.0), ("W2", "J3", 3.0),
+            ("W3", "J1", 5.0)];
+        let mut costs = HashMap::new();
+        for (src_name, dst_name, c) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+            costs.insert((*graph.get_idx_by_name(src_name).unwrap(), *graph.get_idx_by_name(dst_name).unwrap()), c);
+        }
+        let (matching, total_cost) = graph.min_cost_assignment(|src_idx, dst_idx| costs[&(src_idx, dst_idx)]).unwrap();
+        assert_eq!(total_cost, 10.0);
+        let names: Vec<_> = matching.iter()
+            .map(|&(src_idx, dst_idx)| (*graph.get_name_by_idx(src_idx).unwrap(), *graph.get_name_by_idx(dst_idx).unwrap()))
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&("W1", "J2")) && names.contains(&("W2", "J3")) && names.contains(&("W3", "J1")));
+
+        // 冻结后结果相同
+        let expected = graph.max_matching().unwrap().len();
+        let frozen = arena_list.freeze();
+        assert_eq!(frozen.min_cost_assignment(|src_idx, dst_idx| costs[&(src_idx, dst_idx)]).unwrap(), (matching, total_cost));
+        assert_eq!(frozen.max_matching().unwrap().len(), expected);
+        assert!(matches!(frozen.bipartition(), Bipartition::Coloring(_)));
+    }
+
+    #[test]
+    fn test_max_matching_random() {
+        // 随机二分图，与穷举得到的最大匹配数对照
+        let mut rng = Rng::new(11);
+        for _ in 0..50 {
+            let (n_left, n_right) = (1 + rng.gen_range(5), 1 + rng.gen_range(5));
+            let mut arena_list = ArenaList::new();
+            let mut graph = Graph::new(&mut arena_list);
+            for i in 0..n_left + n_right {
+                graph.add_node(i, ());
+            }
+            for _ in 0..rng.gen_range(12) {
+                graph.add_edge(rng.gen_range(n_left), n_left + rng.gen_range(n_right));
+            }
+            let edges = graph.get_all_edges();
+            // 最大匹配数：边的子集中两两不共用端点的最大者
+            let best = (0..1usize << edges.len())
+                .filter(|mask| {
+                    let mut used = HashSet::new();
+                    (0..edges.len()).filter(|i| mask >> i & 1 == 1)
+                        .all(|i| used.insert(edges[i].0) && used.insert(edges[i].1))
+                })
+                .map(|mask| mask.count_ones() as usize)
+                .max()
+                .unwrap();
+            let matching = graph.max_matching().unwrap();
+            assert_eq!(matching.len(), best);
+            let mut used = HashSet::new();
+            for &(src_idx, dst_idx) in &matching {
+                assert!(edges.contains(&(src_idx, dst_idx)));
+                assert!(used.insert(src_idx) && used.insert(dst_idx));
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+/*
+中心性，用来找出图中的枢纽节点，返回值按节点 index 排列
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+- in_degree_centrality / out_degree_centrality：入度（prev_idx）/ 出度（next_idx）除以 n - 1
+- closeness_centrality：按出方向的跳数计算。图不连通时采用 Wasserman-Faust 的修正：
+  (r - 1) / sum(dist) * (r - 1) / (n - 1)，r 为该节点能到达的节点数（含自身），到达不了任何节点时为 0
+- harmonic_centrality：sum(1 / dist)，不可达的节点贡献 0，图不连通时也有意义
+- betweenness_centrality(sources, sample)：Brandes 算法，每个起点做一次 BFS 后反向累加依赖值，O(nm)，不做归一化
+  sources 为 None 时以所有节点为起点，得到精确值；也可以只给一部分起点
+  sample = Some((k, seed)) 时从这些起点中均匀地随机抽 k 个，大图上 O(km)
+  实际使用的起点少于 n 个时，结果按 n / 起点个数 放大，作为近似值
+*/
+
+use std::collections::VecDeque;
+use crate::error::Result;
+use crate::rng::Rng;
+use super::adjacency::{self, Adjacency};
+
+
+// 以下为 Adjacency 上同名方法的实现，Graph 和 FrozenGraph 都可以使用
+pub(super) fn in_degree_centrality<G: Adjacency + ?Sized>(graph: &G) -> Vec<f64> {
+    let scale = degree_scale(graph);
+    (0..graph.node_count()).map(|idx| graph.predecessors(idx).count() as f64 * scale).collect()
+}
+
+pub(super) fn out_degree_centrality<G: Adjacency + ?Sized>(graph: &G) -> Vec<f64> {
+    let scale = degree_scale(graph);
+    (0..graph.node_count()).map(|idx| graph.successors(idx).count() as f64 * scale).collect()
+}
+
+fn degree_scale<G: Adjacency + ?Sized>(graph: &G) -> f64 {
+    let n = graph.node_count();
+    if n > 1 { 1.0 / (n - 1) as f64 } else { 0.0 }
+}
+
+// 从 src_idx 出发沿 successors 的跳数，不可达为 usize::MAX
+fn hop_distances<G: Adjacency + ?Sized>(graph: &G, src_idx: usize) -> Vec<usize> {
+    let mut dist = vec![usize::MAX; graph.node_count()];
+    dist[src_idx] = 0;
+    let mut q = VecDeque::from([src_idx]);
+    while let Some(idx) = q.pop_front() {
+        for next_idx in graph.successors(idx) {
+            if dist[next_idx] == usize::MAX {
+                dist[next_idx] = dist[idx] + 1;
+                q.push_back(next_idx);
+            }
+        }
+    }
+    dist
+}
+
+pub(super) fn closeness_centrality<G: Adjacency + ?Sized>(graph: &G) -> Vec<f64> {
+    let n = graph.node_count();
+    (0..n).map(|idx| {
+        let reachable: Vec<usize> = hop_distances(graph, idx).into_iter().filter(|&d| d != usize::MAX).collect();
+        let total: usize = reachable.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let r = (reachable.len() - 1) as f64;
+        r / total as f64 * r / (n - 1) as f64
+    }).collect()
+}
+
+pub(super) fn harmonic_centrality<G: Adjacency + ?Sized>(graph: &G) -> Vec<f64> {
+    (0..graph.node_count()).map(|idx| {
+        hop_distances(graph, idx).into_iter()
+            .filter(|&d| d != 0 && d != usize::MAX)
+            .map(|d| 1.0 / d as f64)
+            .sum()
+    }).collect()
+}
+
+pub(super) fn betweenness_centrality<G: Adjacency + ?Sized>(graph: &G, sources: Option<&[usize]>, sample: Option<(usize, u64)>) -> Result<Vec<f64>> {
+    let n = graph.node_count();
+    let all_sources: Vec<usize>;
+    let sources = match sources {
+        Some(sources) => {
+            for &idx in sources {
+                adjacency::check_idx(graph, idx)?;
+            }
+            sources
+        }
+        None => {
+            all_sources = (0..n).collect();
+            &all_sources
+        }
+    };
+    // 从候选起点中不放回地随机抽 k 个
+    let sampled: Vec<usize>;
+    let sources = match sample {
+        Some((k, seed)) => {
+            let mut picked = Rng::new(seed).sample(sources.len(), k);
+            picked.sort_unstable();
+            sampled = picked.into_iter().map(|i| sources[i]).collect();
+            &sampled
+        }
+        None => sources,
+    };
+
+    let mut centrality = vec![0.0; n];
+    for &src_idx in sources {
+        // BFS，记录出队顺序、最短路条数 sigma、最短路上的前驱 preds
+        let mut order = Vec::with_capacity(n);
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut sigma = vec![0.0; n];
+        let mut dist = vec![usize::MAX; n];
+        sigma[src_idx] = 1.0;
+        dist[src_idx] = 0;
+        let mut q = VecDeque::from([src_idx]);
+        while let Some(idx) = q.pop_front() {
+            order.push(idx);
+            for next_idx in graph.successors(idx) {
+                if dist[next_idx] == usize::MAX {
+                    dist[next_idx] = dist[idx] + 1;
+                    q.push_back(next_idx);
+                }
+                if dist[next_idx] == dist[idx] + 1 {
+                    sigma[next_idx] += sigma[idx];
+                    preds[next_idx].push(idx);
+                }
+            }
+        }
+        // 按离起点从远到近的顺序累加依赖值
+        let mut delta = vec![0.0; n];
+        for &idx in order.iter().rev() {
+            for &prev_idx in &preds[idx] {
+                delta[prev_idx] += sigma[prev_idx] / sigma[idx] * (1.0 + delta[idx]);
+            }
+            if idx != src_idx {
+                centrality[idx] += delta[idx];
+            }
+        }
+    }
+
+    if !sources.is_empty() && sources.len() < n {
+        let scale = n as f64 / sources.len() as f64;
+        centrality.iter_mut().for_each(|c| *c *= scale);
+    }
+    Ok(centrality)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_centrality() {
+        // 星形：中心 Hub 与四个叶子双向相连
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        for leaf in ["A", "B", "C", "D"] {
+            graph.add_node_and_edge("Hub", (), leaf, ());
+            graph.add_node_and_edge(leaf, (), "Hub", ());
+        }
+        let hub = *graph.get_idx_by_name("Hub").unwrap();
+        let a = *graph.get_idx_by_name("A").unwrap();
+
+        assert_eq!(graph.in_degree_centrality()[hub], 1.0);
+        assert_eq!(graph.out_degree_centrality()[a], 0.25);
+        let closeness = graph.closeness_centrality();
+        assert_eq!(closeness[hub], 1.0);
+        assert!((closeness[a] - 4.0 / 7.0).abs() < 1e-12);
+        let harmonic = graph.harmonic_centrality();
+        assert_eq!(harmonic[hub], 4.0);
+        assert_eq!(harmonic[a], 2.5);
+
+        // 叶子之间的 12 条最短路都经过 Hub
+        let betweenness = graph.betweenness_centrality(None, None).unwrap();
+        assert_eq!(betweenness[hub], 12.0);
+        assert_eq!(betweenness[a], 0.0);
+        // 只从 A、B 出发：Hub 上各有 3 条，放大 5 / 2 倍
+        let sampled = graph.betweenness_centrality(Some(&[a, *graph.get_idx_by_name("B").unwrap()]), None).unwrap();
+        assert_eq!(sampled[hub], 15.0);
+        assert!(graph.betweenness_centrality(Some(&[100]), None).is_err());
+        // 随机抽 2 个叶子作为起点，同样放大 5 / 2 倍；抽 Hub 作为起点时 Hub 上没有贡献
+        let leaves: Vec<usize> = (0..5).filter(|&idx| idx != hub).collect();
+        assert_eq!(graph.betweenness_centrality(Some(&leaves), Some((2, 7))).unwrap()[hub], 15.0);
+        let sampled = graph.betweenness_centrality(None, Some((5, 7))).unwrap();
+        assert_eq!(sampled, graph.betweenness_centrality(None, None).unwrap());
+        assert_eq!(graph.betweenness_centrality(None, Some((3, 1))).unwrap(), graph.betweenness_centrality(None, Some((3, 1))).unwrap());
+
+        // 不连通时，到达不了任何节点的节点为 0
+        graph.add_node("Lonely", ());
+        assert_eq!(*graph.closeness_centrality().last().unwrap(), 0.0);
+
+        // 冻结后结果相同
+        let expected = (graph.closeness_centrality(), graph.harmonic_centrality(), graph.betweenness_centrality(None, None).unwrap());
+        let frozen = arena_list.freeze();
+        assert_eq!((frozen.closeness_centrality(), frozen.harmonic_centrality(), frozen.betweenness_centrality(None, None).unwrap()), expected);
+        assert_eq!(frozen.in_degree_centrality()[hub], 0.8);
+    }
+
+    #[test]
+    fn test_centrality_edge_cases() {
+        let mut arena_list: ArenaList<&str, ()> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 空图
+        assert!(graph.closeness_centrality().is_empty());
+        assert!(graph.betweenness_centrality(None, Some((3, 1))).unwrap().is_empty());
+
+        // 只有一个带自环的节点：度数中心性不除以 0，自环不影响路径类的中心性
+        graph.add_node_and_edge("A", (), "A", ());
+        assert_eq!(graph.in_degree_centrality(), vec![0.0]);
+        assert_eq!(graph.closeness_centrality(), vec![0.0]);
+        assert_eq!(graph.harmonic_centrality(), vec![0.0]);
+        assert_eq!(graph.betweenness_centrality(None, None).unwrap(), vec![0.0]);
+
+        // 两个不相连的链 B -> C -> D、E -> F：只有 C 在最短路上
+        for (src_name, dst_name) in [("B", "C"), ("C", "D"), ("E", "F")] {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let c = *graph.get_idx_by_name("C").unwrap();
+        let betweenness = graph.betweenness_centrality(None, None).unwrap();
+        assert_eq!(betweenness[c], 1.0);
+        assert_eq!(betweenness.iter().sum::<f64>(), 1.0);
+        // 抽 0 个起点时全为 0，抽的个数超过节点数时等于精确值
+        assert!(graph.betweenness_centrality(None, Some((0, 1))).unwrap().iter().all(|&x| x == 0.0));
+        assert_eq!(graph.betweenness_centrality(None, Some((100, 1))).unwrap(), betweenness);
+    }
+}
@@ -0,0 +1,334 @@
+/*
+着色与独立集，把有向边看作无向边，忽略自环
+- 着色结果按节点 index 排列，颜色为 0..颜色数，相邻节点颜色不同
+- dsatur_coloring：每次给饱和度（邻居已用的不同颜色数）最大的节点着色，平局取度数大的，再平局取 index 小的
+  用大顶堆按 (饱和度, 度数) 取节点，饱和度变化时压入新记录，过时的记录弹出时跳过，O((n + m) log n)
+- welsh_powell_coloring：节点按度数从大到小排列，每种颜色依次扫一遍，能涂就涂
+- exact_coloring：回溯 + 剪枝，以 DSatur 的结果为初始上界，只适合小图
+  max_steps 限制尝试着色的次数，用完后返回目前最好的结果，第二个返回值表示是否已证明最优
+- maximal_independent_set：贪心，每次选剩余度数最小的节点，得到极大（不一定最大）独立集
+- maximal_cliques / maximum_clique：带 pivot 的 Bron-Kerbosch，
+  _names 版本直接返回节点名（匿名节点为 None）
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用；_names 版本只在 Graph 上
+*/
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use super::adjacency::Adjacency;
+use super::bipartite::undirected_neighbors;
+use super::Graph;
+
+
+// 只在本模块用到：去掉自环后的无向邻居
+fn neighbor_sets<G: Adjacency + ?Sized>(graph: &G) -> Vec<HashSet<usize>> {
+    undirected_neighbors(graph).into_iter().enumerate()
+        .map(|(idx, neighbors)| neighbors.into_iter().filter(|&next_idx| next_idx != idx).collect())
+        .collect()
+}
+
+
+// Adjacency::dsatur_coloring 等的实现
+pub(super) fn dsatur_coloring<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let neighbors = neighbor_sets(graph);
+    let n = neighbors.len();
+    let mut colors = vec![usize::MAX; n];
+    // 每个节点的邻居已经用过的颜色
+    let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut heap: BinaryHeap<(usize, usize, Reverse<usize>)> = (0..n)
+        .map(|idx| (0, neighbors[idx].len(), Reverse(idx)))
+        .collect();
+    while let Some((sat, _, Reverse(idx))) = heap.pop() {
+        if colors[idx] != usize::MAX || sat != saturation[idx].len() {
+            continue;
+        }
+        let color = (0..).find(|c| !saturation[idx].contains(c)).unwrap();
+        colors[idx] = color;
+        for &next_idx in &neighbors[idx] {
+            if colors[next_idx] == usize::MAX && saturation[next_idx].insert(color) {
+                heap.push((saturation[next_idx].len(), neighbors[next_idx].len(), Reverse(next_idx)));
+            }
+        }
+    }
+    colors
+}
+
+pub(super) fn welsh_powell_coloring<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let neighbors = neighbor_sets(graph);
+    let n = neighbors.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&idx| (usize::MAX - neighbors[idx].len(), idx));
+    let mut colors = vec![usize::MAX; n];
+    let mut colored = 0;
+    let mut color = 0;
+    while colored < n {
+        for &idx in &order {
+            if colors[idx] == usize::MAX && neighbors[idx].iter().all(|&next_idx| colors[next_idx] != color) {
+                colors[idx] = color;
+                colored += 1;
+            }
+        }
+        color += 1;
+    }
+    colors
+}
+
+// 返回 (着色, 是否已证明最优)
+pub(super) fn exact_coloring<G: Adjacency + ?Sized>(graph: &G, max_steps: usize) -> (Vec<usize>, bool) {
+    let neighbors = neighbor_sets(graph);
+    let n = neighbors.len();
+    let mut best = dsatur_coloring(graph);
+    let mut best_count = best.iter().map(|&c| c + 1).max().unwrap_or(0);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&idx| (usize::MAX - neighbors[idx].len(), idx));
+
+    // stack[depth] = order[depth] 下一个要尝试的颜色；used[depth] = 给前 depth 个节点着色时用到的颜色数
+    let mut colors = vec![usize::MAX; n];
+    let mut stack = vec![0];
+    let mut used = vec![0; n + 1];
+    let mut steps = 0;
+    while let Some(&color) = stack.last() {
+        let depth = stack.len() - 1;
+        if depth == n {
+            // 找到更好的着色，之后只找颜色更少的
+            best.clone_from(&colors);
+            best_count = used[n];
+            stack.pop();
+            continue;
+        }
+        let idx = order[depth];
+        colors[idx] = usize::MAX;
+        // 新颜色只能是 used[depth]（对称性剪枝），且总颜色数要少于当前最优
+        if color > used[depth] || color + 1 >= best_count {
+            stack.pop();
+            continue;
+        }
+        if steps >= max_steps {
+            return (best, false);
+        }
+        steps += 1;
+        *stack.last_mut().unwrap() += 1;
+        if neighbors[idx].iter().any(|&next_idx| colors[next_idx] == color) {
+            continue;
+        }
+        colors[idx] = color;
+        used[depth + 1] = used[depth].max(color + 1);
+        stack.push(0);
+    }
+    (best, true)
+}
+
+pub(super) fn maximal_independent_set<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let neighbors = neighbor_sets(graph);
+    let n = neighbors.len();
+    let mut removed = vec![false; n];
+    let mut degree: Vec<usize> = neighbors.iter().map(|neighbors| neighbors.len()).collect();
+    let mut res = Vec::new();
+    while let Some(idx) = (0..n).filter(|&idx| !removed[idx]).min_by_key(|&idx| (degree[idx], idx)) {
+        res.push(idx);
+        // 删除选中的节点和它的邻居，更新剩余节点的度数
+        for removed_idx in std::iter::once(idx).chain(neighbors[idx].iter().copied()) {
+            if !removed[removed_idx] {
+                removed[removed_idx] = true;
+                for &next_idx in &neighbors[removed_idx] {
+                    degree[next_idx] = degree[next_idx].saturating_sub(1);
+                }
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+// 所有极大团，每个团内的节点 index 从小到大排列。空图没有团
+pub(super) fn maximal_cliques<G: Adjacency + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    let neighbors = neighbor_sets(graph);
+    let mut res = Vec::new();
+    if neighbors.is_empty() {
+        return res;
+    }
+    bron_kerbosch(&neighbors, &mut Vec::new(), (0..neighbors.len()).collect(), Vec::new(), &mut res);
+    for clique in res.iter_mut() {
+        clique.sort_unstable();
+    }
+    res
+}
+
+// 最大团，有多个时取 index 字典序最小的
+pub(super) fn maximum_clique<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let mut cliques = maximal_cliques(graph);
+    cliques.sort();
+    cliques.into_iter().rev().max_by_key(|clique| clique.len()).unwrap_or_default()
+}
+
+
+impl<'a, K, T> Graph<'a, K, T> {
+    pub fn maximal_independent_set_names(&self) -> Vec<Option<&K>> {
+        self.get_names_by_idxes(&self.maximal_independent_set())
+    }
+
+    pub fn maximum_clique_names(&self) -> Vec<Option<&K>> {
+        self.get_names_by_idxes(&self.maximum_clique())
+    }
+}
+
+
+// r：当前的团；p：可以加入的候选节点；x：已经处理过的节点（用来判断极大）
+fn bron_kerbosch(neighbors: &[HashSet<usize>], r: &mut Vec<usize>, mut p: Vec<usize>, mut x: Vec<usize>, res: &mut Vec<Vec<usize>>) {
+    if p.is_empty() {
+        if x.is_empty() {
+            res.push(r.clone());
+        }
+        return;
+    }
+    // pivot 取 p ∪ x 中在 p 里邻居最多的节点，它的邻居不必作为分支
+    let pivot = p.iter().chain(&x)
+        .copied()
+        .max_by_key(|&u| p.iter().filter(|v| neighbors[u].contains(v)).count())
+        .unwrap();
+    let branches: Vec<usize> = p.iter().copied().filter(|v| !neighbors[pivot].contains(v)).collect();
+    for v in branches {
+        r.push(v);
+        let next_p = p.iter().copied().filter(|u| neighbors[v].contains(u)).collect();
+        let next_x = x.iter().copied().filter(|u| neighbors[v].contains(u)).collect();
+        bron_kerbosch(neighbors, r, next_p, next_x, res);
+        r.pop();
+        p.retain(|&u| u != v);
+        x.push(v);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::rng::Rng;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_coloring() {
+        // 冲突图：五边形 A-B-C-D-E-A，外加 F 与 A、B 相连（A-B-F 构成三角形）
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("B", "C"), ("C", "D"), ("D", "E"), ("E", "A"), ("F", "A"), ("B", "F"), ("F", "F")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let edges = graph.get_all_edges();
+        let is_proper = |colors: &[usize]| edges.iter().all(|&(src_idx, dst_idx)| src_idx == dst_idx || colors[src_idx] != colors[dst_idx]);
+        let count = |colors: &[usize]| colors.iter().max().unwrap() + 1;
+
+        for colors in [graph.dsatur_coloring(), graph.welsh_powell_coloring()] {
+            assert!(is_proper(&colors));
+            assert!(count(&colors) <= 4);
+        }
+        // 奇环需要 3 种颜色
+        let (colors, optimal) = graph.exact_coloring(10000);
+        assert!(is_proper(&colors));
+        assert_eq!(count(&colors), 3);
+        assert!(optimal);
+        let (colors, optimal) = graph.exact_coloring(0);
+        assert!(is_proper(&colors));
+        assert!(!optimal || count(&colors) == 3);
+
+        let independent = graph.maximal_independent_set();
+        assert!(independent.iter().all(|&idx1| independent.iter().all(|&idx2| !edges.contains(&(idx1, idx2)) || idx1 == idx2)));
+        assert_eq!(graph.get_names_by_idxes(&independent), vec![Some(&"C"), Some(&"E"), Some(&"F")]);
+
+        let mut clique = graph.maximum_clique_names();
+        clique.sort();
+        assert_eq!(clique, vec![Some(&"A"), Some(&"B"), Some(&"F")]);
+        assert_eq!(graph.maximal_cliques().len(), 5);
+
+        // 冻结后结果相同
+        let expected = (graph.dsatur_coloring(), graph.welsh_powell_coloring(), graph.maximal_cliques());
+        let frozen = arena_list.freeze();
+        assert_eq!((frozen.dsatur_coloring(), frozen.welsh_powell_coloring(), frozen.maximal_cliques()), expected);
+        assert_eq!(frozen.maximal_independent_set(), independent);
+        assert_eq!(count(&frozen.exact_coloring(10000).0), 3);
+    }
+
+    #[test]
+    fn test_coloring_edge_cases() {
+        let mut arena_list: ArenaList<&str, ()> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 空图
+        assert!(graph.dsatur_coloring().is_empty());
+        assert!(graph.welsh_powell_coloring().is_empty());
+        assert_eq!(graph.exact_coloring(0), (Vec::new(), true));
+        assert!(graph.maximal_independent_set().is_empty());
+        assert!(graph.maximum_clique().is_empty());
+        assert!(graph.maximal_cliques().is_empty());
+
+        // 孤立节点和自环：一种颜色，独立集包含全部节点
+        graph.add_node_and_edge("A", (), "A", ());
+        graph.add_node("B", ());
+        assert_eq!(graph.dsatur_coloring(), vec![0, 0]);
+        assert_eq!(graph.exact_coloring(100), (vec![0, 0], true));
+        assert_eq!(graph.maximal_independent_set(), vec![0, 1]);
+        assert_eq!(graph.maximal_cliques().len(), 2);
+    }
+
+    #[test]
+    fn test_coloring_random() {
+        // 随机小图，与穷举得到的色数、最大团大小对照
+        let mut rng = Rng::new(5);
+        for _ in 0..20 {
+            let n = 1 + rng.gen_range(7);
+            let mut arena_list = ArenaList::new();
+            let mut graph = Graph::new(&mut arena_list);
+            for i in 0..n {
+                graph.add_node(i, ());
+            }
+            for _ in 0..rng.gen_range(15) {
+                graph.add_edge(rng.gen_range(n), rng.gen_range(n));
+            }
+            let edges: Vec<(usize, usize)> = graph.get_all_edges().into_iter().filter(|(src_idx, dst_idx)| src_idx != dst_idx).collect();
+            let adjacent = |u: usize, v: usize| edges.contains(&(u, v)) || edges.contains(&(v, u));
+            let is_proper = |colors: &[usize]| edges.iter().all(|&(src_idx, dst_idx)| colors[src_idx] != colors[dst_idx]);
+            let count = |colors: &[usize]| colors.iter().max().map_or(0, |c| c + 1);
+
+            // 色数：最小的 k，使 k^n 种着色中有一种合法
+            let chromatic = (1..=n).find(|&k| {
+                (0..k.pow(n as u32)).any(|code| {
+                    let colors: Vec<usize> = (0..n).map(|i| code / k.pow(i as u32) % k).collect();
+                    is_proper(&colors)
+                })
+            }).unwrap();
+            let (colors, optimal) = graph.exact_coloring(1_000_000);
+            assert!(optimal && is_proper(&colors));
+            assert_eq!(count(&colors), chromatic);
+            for colors in [graph.dsatur_coloring(), graph.welsh_powell_coloring()] {
+                assert!(is_proper(&colors) && count(&colors) >= chromatic);
+            }
+
+            // DSatur 与逐个扫描取最大饱和度的朴素实现结果相同
+            let mut naive = vec![usize::MAX; n];
+            for _ in 0..n {
+                let used = |idx: usize| -> HashSet<usize> {
+                    (0..n).filter(|&v| adjacent(idx, v) && naive[v] != usize::MAX).map(|v| naive[v]).collect()
+                };
+                let degree = |idx: usize| (0..n).filter(|&v| adjacent(idx, v)).count();
+                let idx = (0..n)
+                    .filter(|&idx| naive[idx] == usize::MAX)
+                    .max_by_key(|&idx| (used(idx).len(), degree(idx), usize::MAX - idx))
+                    .unwrap();
+                let used = used(idx);
+                naive[idx] = (0..).find(|c| !used.contains(c)).unwrap();
+            }
+            assert_eq!(graph.dsatur_coloring(), naive);
+
+            // 最大团：所有子集中两两相邻的最大者
+            let clique_size = (0..1usize << n)
+                .filter(|mask| (0..n).all(|u| (0..n).all(|v| u == v || mask >> u & 1 == 0 || mask >> v & 1 == 0 || adjacent(u, v))))
+                .map(|mask| mask.count_ones() as usize)
+                .max()
+                .unwrap();
+            assert_eq!(graph.maximum_clique().len(), clique_size);
+
+            // 独立集：两两不相邻，且其它节点都与集合中的某个节点相邻
+            let independent = graph.maximal_independent_set();
+            assert!(independent.iter().all(|&u| independent.iter().all(|&v| !adjacent(u, v))));
+            assert!((0..n).all(|u| independent.contains(&u) || independent.iter().any(|&v| adjacent(u, v))));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+/*
+社区发现，把有向边看作无向边。只依赖拓扑结构和边权，写在 Adjacency 上，FrozenGraph 也能用
+- 边权由 weight(src_idx, dst_idx) 给出，不带权时传入 |_, _| 1.0
+  A->B 与 B->A 同时存在时，两者的权重相加；自环在 louvain 和模块度中计入，在标签传播中忽略
+- label_propagation：每个节点反复采用邻居中权重最大的标签，平局随机挑选，直到标签不再变化，近似 O(m)
+- louvain：贪心地把节点移入使模块度增加最多的社区，再把社区收缩为节点，重复到模块度不再增加
+- 随机性（遍历顺序、平局）都来自 CommunityOptions::seed，相同的 seed 得到相同的结果
+- 返回 Communities：每个节点的社区编号（0..count，按社区中最小的节点 index 排序）和模块度
+- modularity(ids, weight)：任意划分的模块度，ids 长度与节点个数不符或编号越界时返回错误
+*/
+
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use super::Adjacency;
+
+
+pub struct CommunityOptions {
+    pub seed: u64,
+    // label_propagation 的最大轮数；louvain 中每一层局部移动的最大轮数
+    pub max_iter: usize,
+}
+
+impl Default for CommunityOptions {
+    fn default() -> Self {
+        Self { seed: 0, max_iter: 100 }
+    }
+}
+
+pub struct Communities {
+    // 每个节点所在社区的编号，按节点 index 排列
+    pub ids: Vec<usize>,
+    pub count: usize,
+    pub modularity: f64,
+}
+
+
+// 无向带权图：邻居（不含自身）及权重，self_weight[i] 为自环在度数中的贡献（自环权重的两倍）
+struct Undirected {
+    neighbors: Vec<Vec<(usize, f64)>>,
+    self_weight: Vec<f64>,
+}
+
+impl Undirected {
+    // 每个节点的加权度数
+    fn degrees(&self) -> Vec<f64> {
+        self.neighbors.iter().zip(&self.self_weight)
+            .map(|(neighbors, self_weight)| neighbors.iter().map(|&(_, w)| w).sum::<f64>() + self_weight)
+            .collect()
+    }
+
+    // 模块度，调用方保证 ids 合法
+    fn modularity(&self, ids: &[usize]) -> f64 {
+        let degrees = self.degrees();
+        let m2: f64 = degrees.iter().sum();
+        if m2 == 0.0 {
+            return 0.0;
+        }
+        let n = ids.len();
+        let mut inner = vec![0.0; n];
+        let mut total = vec![0.0; n];
+        for (idx, neighbors) in self.neighbors.iter().enumerate() {
+            total[ids[idx]] += degrees[idx];
+            inner[ids[idx]] += self.self_weight[idx];
+            for &(next_idx, w) in neighbors {
+                if ids[idx] == ids[next_idx] {
+                    inner[ids[idx]] += w;
+                }
+            }
+        }
+        inner.iter().zip(&total).map(|(inner, total)| inner / m2 - (total / m2).powi(2)).sum()
+    }
+
+    // 按 ids 把每个社区收缩为一个节点，ids 的取值为 0..count
+    fn aggregate(&self, ids: &[usize], count: usize) -> Self {
+        let mut weights = vec![Vec::new(); count];
+        let mut self_weight = vec![0.0; count];
+        for (idx, neighbors) in self.neighbors.iter().enumerate() {
+            self_weight[ids[idx]] += self.self_weight[idx];
+            for &(next_idx, w) in neighbors {
+                if ids[idx] == ids[next_idx] {
+                    self_weight[ids[idx]] += w;
+                } else {
+                    weights[ids[idx]].push((ids[next_idx], w));
+                }
+            }
+        }
+        // 合并指向同一个社区的边
+        let neighbors = weights.into_iter()
+            .map(|mut edges: Vec<(usize, f64)>| {
+                edges.sort_by_key(|&(idx, _)| idx);
+                let mut merged: Vec<(usize, f64)> = Vec::new();
+                for (idx, w) in edges {
+                    match merged.last_mut() {
+                        Some((last_idx, last_w)) if *last_idx == idx => *last_w += w,
+                        _ => merged.push((idx, w)),
+                    }
+                }
+                merged
+            })
+            .collect();
+        Self { neighbors, self_weight }
+    }
+}
+
+
+// 把任意的社区标签重新编号为 0..count，按社区中最小的节点 index 排序
+fn renumber(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut mapping = vec![usize::MAX; labels.len()];
+    let mut count = 0;
+    let ids = labels.iter().map(|&label| {
+        if mapping[label] == usize::MAX {
+            mapping[label] = count;
+            count += 1;
+        }
+        mapping[label]
+    }).collect();
+    (ids, count)
+}
+
+
+// 转为无向带权图，A->B 与 B->A 的权重相加
+fn to_undirected<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64) -> Undirected {
+    let n = graph.node_count();
+    let mut neighbors = vec![Vec::new(); n];
+    let mut self_weight = vec![0.0; n];
+    let edges = (0..n).flat_map(|src_idx| graph.successors(src_idx).map(move |dst_idx| (src_idx, dst_idx)));
+    for (src_idx, dst_idx) in edges {
+        let w = weight(src_idx, dst_idx);
+        if src_idx == dst_idx {
+            self_weight[src_idx] += 2.0 * w;
+        } else {
+            neighbors[src_idx].push((dst_idx, w));
+            neighbors[dst_idx].push((src_idx, w));
+        }
+    }
+    // 节点 index 就是社区编号，直接复用 aggregate 合并重复的邻居
+    Undirected { neighbors, self_weight }.aggregate(&(0..n).collect::<Vec<_>>(), n)
+}
+
+// 以下为 Adjacency 上同名方法的实现，Graph 和 FrozenGraph 都可以使用
+// 给定社区划分的模块度。ids 按节点 index 排列，长度须等于节点个数，取值须小于节点个数
+pub(super) fn modularity<G: Adjacency + ?Sized>(graph: &G, ids: &[usize], weight: impl Fn(usize, usize) -> f64) -> Result<f64> {
+    let n = graph.node_count();
+    if ids.len() != n {
+        // 多出来的 id 对应不存在的节点 n；缺少的从第 ids.len() 个节点开始
+        return Err(Error::UnknownNode(ids.len().min(n)));
+    }
+    if let Some(&id) = ids.iter().find(|&&id| id >= n) {
+        return Err(Error::OutOfRange(id));
+    }
+    Ok(to_undirected(graph, weight).modularity(ids))
+}
+
+pub(super) fn label_propagation<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64, opts: &CommunityOptions) -> Communities {
+    let undirected = to_undirected(graph, &weight);
+    let n = undirected.neighbors.len();
+    let mut rng = Rng::new(opts.seed);
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    // label_weight[label]：当前节点的邻居中该标签的总权重，touched 记录用到的标签，用完清零
+    let mut label_weight = vec![0.0; n];
+    let mut touched = Vec::new();
+    let mut best = Vec::new();
+
+    for _ in 0..opts.max_iter {
+        rng.shuffle(&mut order);
+        let mut changed = false;
+        for &idx in &order {
+            for &(next_idx, w) in &undirected.neighbors[idx] {
+                if label_weight[labels[next_idx]] == 0.0 {
+                    touched.push(labels[next_idx]);
+                }
+                label_weight[labels[next_idx]] += w;
+            }
+            let max_weight = touched.iter().map(|&label| label_weight[label]).fold(0.0, f64::max);
+            best.clear();
+            best.extend(touched.iter().copied().filter(|&label| label_weight[label] == max_weight));
+            // 当前标签已经是最优之一时保持不变，避免来回震荡
+            if !best.is_empty() && !best.contains(&labels[idx]) {
+                labels[idx] = best[rng.gen_range(best.len())];
+                changed = true;
+            }
+            for label in touched.drain(..) {
+                label_weight[label] = 0.0;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let (ids, count) = renumber(&labels);
+    let modularity = undirected.modularity(&ids);
+    Communities { ids, count, modularity }
+}
+
+pub(super) fn louvain<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64, opts: &CommunityOptions) -> Communities {
+    let mut undirected = to_undirected(graph, &weight);
+    let mut rng = Rng::new(opts.seed);
+    // membership[原图节点] = 当前层的节点
+    let mut membership: Vec<usize> = (0..undirected.neighbors.len()).collect();
+
+    loop {
+        let n = undirected.neighbors.len();
+        let degrees = undirected.degrees();
+        let m2: f64 = degrees.iter().sum();
+        if m2 == 0.0 {
+            break;
+        }
+        let mut comm: Vec<usize> = (0..n).collect();
+        let mut total = degrees.clone();
+        let mut order: Vec<usize> = (0..n).collect();
+        rng.shuffle(&mut order);
+        let mut comm_weight = vec![0.0; n];
+        let mut touched = Vec::new();
+
+        let mut improved = false;
+        for _ in 0..opts.max_iter {
+            let mut moved = false;
+            for &idx in &order {
+                let old = comm[idx];
+                touched.push(old);
+                for &(next_idx, w) in &undirected.neighbors[idx] {
+                    if comm_weight[comm[next_idx]] == 0.0 && comm[next_idx] != old {
+                        touched.push(comm[next_idx]);
+                    }
+                    comm_weight[comm[next_idx]] += w;
+                }
+                // 先把节点移出原社区，再挑选增益最大的社区（包括原社区）
+                total[old] -= degrees[idx];
+                let gain = |c: usize| comm_weight[c] - total[c] * degrees[idx] / m2;
+                let mut best = old;
+                for &c in &touched {
+                    if gain(c) > gain(best) + 1e-12 {
+                        best = c;
+                    }
+                }
+                total[best] += degrees[idx];
+                if best != old {
+                    comm[idx] = best;
+                    moved = true;
+                }
+                for c in touched.drain(..) {
+                    comm_weight[c] = 0.0;
+                }
+            }
+            if !moved {
+                break;
+            }
+            improved = true;
+        }
+        if !improved {
+            break;
+        }
+
+        let (ids, count) = renumber(&comm);
+        for m in membership.iter_mut() {
+            *m = ids[*m];
+        }
+        undirected = undirected.aggregate(&ids, count);
+    }
+
+    let (ids, count) = renumber(&membership);
+    let modularity = to_undirected(graph, weight).modularity(&ids);
+    Communities { ids, count, modularity }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, CommunityOptions, Graph};
+    use crate::Error;
+
+    #[test]
+    fn test_community() {
+        // 两个三角形，由 A3 - B3 相连
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A1", "A2"), ("A2", "A3"), ("A3", "A1"), ("B1", "B2"), ("B2", "B3"), ("B3", "B1"), ("A3", "B3")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let opts = CommunityOptions::default();
+
+        let louvain = graph.louvain(|_, _| 1.0, &opts);
+        assert_eq!(louvain.ids, vec![0, 0, 0, 1, 1, 1]);
+        assert_eq!(louvain.count, 2);
+        assert!((louvain.modularity - 5.0 / 14.0).abs() < 1e-12);
+        assert_eq!(graph.modularity(&[0; 6], |_, _| 1.0), Ok(0.0));
+        // ids 与节点个数不符、社区编号越界：返回错误而不是 panic
+        assert_eq!(graph.modularity(&[0; 5], |_, _| 1.0), Err(Error::UnknownNode(5)));
+        assert_eq!(graph.modularity(&[0; 7], |_, _| 1.0), Err(Error::UnknownNode(6)));
+        assert_eq!(graph.modularity(&[0, 0, 0, 1, 1, 9], |_, _| 1.0), Err(Error::OutOfRange(9)));
+
+        // 相同的 seed 结果相同
+        let lpa = graph.label_propagation(|_, _| 1.0, &opts);
+        let again = graph.label_propagation(|_, _| 1.0, &opts);
+        assert_eq!(lpa.ids, again.ids);
+        assert!((lpa.modularity - graph.modularity(&lpa.ids, |_, _| 1.0).unwrap()).abs() < 1e-12);
+
+        // 去掉桥之后，两个三角形各自成为一个社区
+        graph.del_edge_by_name("A3", "B3");
+        for seed in 0..5 {
+            let lpa = graph.label_propagation(|_, _| 1.0, &CommunityOptions { seed, ..CommunityOptions::default() });
+            assert_eq!(lpa.ids, vec![0, 0, 0, 1, 1, 1]);
+        }
+
+        // 冻结后结果相同
+        let expected = graph.louvain(|_, _| 1.0, &opts);
+        let frozen = arena_list.freeze();
+        let louvain = frozen.louvain(|_, _| 1.0, &opts);
+        assert_eq!((louvain.ids, louvain.count), (expected.ids, expected.count));
+        assert!((louvain.modularity - expected.modularity).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,55 @@
+/*
+弱连通分量：把边看作无向边（同时看 next_idx 和 prev_idx）后的连通分量
+- 用并查集实现，to_union_find 返回的并查集可以留着复用：
+  新增节点后 push，新增边后 union，就能继续查询，不必重新计算
+- 写在 Adjacency 上，FrozenGraph 也能用
+- 删除节点/边之后，节点 index 可能变化，分量也可能拆开，需要重新调用 to_union_find
+*/
+
+use crate::union_find::UnionFind;
+use super::Adjacency;
+
+
+// Adjacency::to_union_find 的实现：按图中现有的边构建并查集，元素编号就是节点 index
+pub(super) fn to_union_find<G: Adjacency + ?Sized>(graph: &G) -> UnionFind {
+    let mut uf = UnionFind::new(graph.node_count());
+    for src_idx in 0..graph.node_count() {
+        for dst_idx in graph.successors(src_idx) {
+            uf.union(src_idx, dst_idx);
+        }
+    }
+    uf
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_components() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("John", "Emma"), ("Tom", "Emma"), ("Lucy", "Oliver"), ("Daniel", "Daniel")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        assert_eq!(graph.weakly_connected_components(), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(graph.component_ids(), vec![0, 0, 0, 1, 1, 2]);
+
+        // 新增节点、边后复用并查集
+        let mut uf = graph.to_union_find();
+        let idx = graph.add_node("Sophia", ());
+        assert_eq!(uf.push(), idx);
+        graph.add_edge(idx, 5);
+        uf.union(idx, 5);
+        graph.add_edge(4, 0);
+        uf.union(4, 0);
+        assert_eq!(uf.count(), 2);
+        assert_eq!(uf.component_ids(), graph.component_ids());
+
+        // 冻结后结果相同
+        let expected = graph.weakly_connected_components();
+        assert_eq!(arena_list.freeze().weakly_connected_components(), expected);
+    }
+}
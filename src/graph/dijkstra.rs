@@ -0,0 +1,67 @@
+/*
+Dijkstra 单源最短路，边权需非负（有负权时用 bellman_ford/spfa）
+- 只在模块内部使用：johnson 重新赋权后、k_shortest_paths 求偏离路径时调用，两种图都可以传入
+- weight(src_idx, dst_idx) 返回 f64::INFINITY 的边视为不存在，可以用来临时屏蔽节点或边
+- 用 MinScored 做小顶堆，过时的记录弹出时跳过，O(m log m)
+*/
+
+use std::collections::BinaryHeap;
+use crate::error::Result;
+use super::adjacency::{self, Adjacency};
+use super::scored::MinScored;
+use super::ShortestPaths;
+
+
+pub(super) fn dijkstra<G: Adjacency + ?Sized>(graph: &G, src_idx: usize, weight: impl Fn(usize, usize) -> f64) -> Result<ShortestPaths> {
+    adjacency::check_idx(graph, src_idx)?;
+    let n = graph.node_count();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut prev = vec![None; n];
+    let mut heap = BinaryHeap::new();
+    dist[src_idx] = 0.0;
+    heap.push(MinScored(0.0, src_idx));
+
+    while let Some(MinScored(d, idx)) = heap.pop() {
+        if d > dist[idx] {
+            continue;
+        }
+        for next_idx in graph.successors(idx) {
+            let new_dist = d + weight(idx, next_idx);
+            if new_dist < dist[next_idx] {
+                dist[next_idx] = new_dist;
+                prev[next_idx] = Some(idx);
+                heap.push(MinScored(new_dist, next_idx));
+            }
+        }
+    }
+    Ok(ShortestPaths { src_idx, dist, prev })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::dijkstra::dijkstra;
+    use crate::graph::{ArenaList, Graph};
+
+    #[test]
+    fn test_dijkstra() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("A", "C"), ("B", "D"), ("C", "D"), ("D", "E")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let [a, b, c, d, e] = ["A", "B", "C", "D", "E"].map(|name| *graph.get_idx_by_name(name).unwrap());
+        let weight = |src_idx, dst_idx| if (src_idx, dst_idx) == (a, b) { 5.0 } else { 1.0 };
+        let paths = dijkstra(&graph, a, weight).unwrap();
+        assert_eq!(paths.dist[e], 3.0);
+        assert_eq!(paths.path_to(e), Some(vec![a, c, d, e]));
+
+        // 屏蔽 C -> D 后只能绕道 B
+        let blocked = |src_idx, dst_idx| if (src_idx, dst_idx) == (c, d) { f64::INFINITY } else { weight(src_idx, dst_idx) };
+        let paths = dijkstra(&graph, a, blocked).unwrap();
+        assert_eq!(paths.path_to(e), Some(vec![a, b, d, e]));
+        assert_eq!(paths.path_to(a), Some(vec![a]));
+        assert!(dijkstra(&graph, 100, weight).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+/*
+欧拉路径 / 欧拉回路（有向图）：每条边恰好经过一次
+- 欧拉回路存在：每个节点入度（prev_idx）等于出度（next_idx），且有边的节点都在同一个弱连通分量中
+- 欧拉路径存在：满足上面的条件，或者恰好一个节点出度比入度多 1（起点）、一个节点入度比出度多 1（终点），其余平衡
+- 构造用 Hierholzer 算法，显式的栈，O(m)
+- 返回边的序列 (src_idx, dst_idx)，前一条边的终点是后一条边的起点；不存在时返回 None
+- 没有边的图：存在（空的）欧拉回路
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+*/
+
+use super::adjacency::Adjacency;
+
+
+// 有边的节点是否都在同一个弱连通分量中
+fn edges_connected<G: Adjacency + ?Sized>(graph: &G) -> bool {
+    let mut uf = graph.to_union_find();
+    let mut with_edges = (0..graph.node_count())
+        .filter(|&idx| graph.successors(idx).next().is_some() || graph.predecessors(idx).next().is_some());
+    match with_edges.next() {
+        Some(first) => with_edges.all(|idx| uf.connected(first, idx)),
+        None => true,
+    }
+}
+
+// 欧拉路径的起点：回路时为第一个有出边的节点；度数不满足条件时返回 None
+fn euler_start<G: Adjacency + ?Sized>(graph: &G, circuit: bool) -> Option<Option<usize>> {
+    let n = graph.node_count();
+    let (mut start, mut end) = (None, None);
+    for idx in 0..n {
+        let (out_degree, in_degree) = (graph.successors(idx).count(), graph.predecessors(idx).count());
+        if out_degree == in_degree + 1 && !circuit && start.is_none() {
+            start = Some(idx);
+        } else if in_degree == out_degree + 1 && !circuit && end.is_none() {
+            end = Some(idx);
+        } else if out_degree != in_degree {
+            return None;
+        }
+    }
+    if start.is_some() != end.is_some() || !edges_connected(graph) {
+        return None;
+    }
+    Some(start.or_else(|| (0..n).find(|&idx| graph.successors(idx).next().is_some())))
+}
+
+// Adjacency::has_eulerian_circuit 等的实现
+pub(super) fn has_eulerian_circuit<G: Adjacency + ?Sized>(graph: &G) -> bool {
+    euler_start(graph, true).is_some()
+}
+
+pub(super) fn has_eulerian_path<G: Adjacency + ?Sized>(graph: &G) -> bool {
+    euler_start(graph, false).is_some()
+}
+
+pub(super) fn eulerian_circuit<G: Adjacency + ?Sized>(graph: &G) -> Option<Vec<(usize, usize)>> {
+    euler_start(graph, true).map(|start| hierholzer(graph, start))
+}
+
+pub(super) fn eulerian_path<G: Adjacency + ?Sized>(graph: &G) -> Option<Vec<(usize, usize)>> {
+    euler_start(graph, false).map(|start| hierholzer(graph, start))
+}
+
+fn hierholzer<G: Adjacency + ?Sized>(graph: &G, start: Option<usize>) -> Vec<(usize, usize)> {
+    let Some(start) = start else { return Vec::new() };
+    // 每个节点还没走过的出边
+    let mut remaining: Vec<Vec<usize>> = (0..graph.node_count())
+        .map(|idx| {
+            let mut next_idxes: Vec<usize> = graph.successors(idx).collect();
+            next_idxes.sort_unstable_by(|a, b| b.cmp(a));
+            next_idxes
+        })
+        .collect();
+    // 沿未走过的边一直往前走，走不动时把节点弹出，弹出的顺序反过来就是欧拉路径
+    let mut stack = vec![start];
+    let mut path = Vec::new();
+    while let Some(&idx) = stack.last() {
+        match remaining[idx].pop() {
+            Some(next_idx) => stack.push(next_idx),
+            None => path.push(stack.pop().unwrap()),
+        }
+    }
+    path.reverse();
+    path.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_euler() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("B", "C"), ("C", "A"), ("A", "D"), ("D", "E"), ("E", "A")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let check = |edges: &[(usize, usize)], total: usize| {
+            assert_eq!(edges.len(), total);
+            assert!(edges.windows(2).all(|pair| pair[0].1 == pair[1].0));
+            let mut sorted = edges.to_vec();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), total);
+        };
+
+        // 两个共享 A 的环：存在回路
+        assert!(graph.has_eulerian_circuit());
+        let circuit = graph.eulerian_circuit().unwrap();
+        check(&circuit, 6);
+        assert_eq!(circuit[0].0, circuit[5].1);
+
+        // 加一条 B -> D：起点 B，终点 D 以外的节点平衡，只有路径
+        let [b, d] = ["B", "D"].map(|name| *graph.get_idx_by_name(name).unwrap());
+        graph.add_edge(b, d);
+        assert!(!graph.has_eulerian_circuit());
+        assert!(graph.has_eulerian_path());
+        let path = graph.eulerian_path().unwrap();
+        check(&path, 7);
+        assert_eq!((path[0].0, path[6].1), (b, d));
+
+        // 不连通的环：两者都不存在
+        graph.add_node_and_edge("X", (), "Y", ());
+        graph.add_node_and_edge("Y", (), "X", ());
+        assert!(!graph.has_eulerian_path());
+        assert_eq!(graph.eulerian_circuit(), None);
+
+        // 删掉 X、Y 后冻结，结果相同
+        graph.del_node_by_name("X");
+        graph.del_node_by_name("Y");
+        let expected = graph.eulerian_path();
+        let frozen = arena_list.freeze();
+        assert!(frozen.has_eulerian_path() && !frozen.has_eulerian_circuit());
+        assert_eq!(frozen.eulerian_path(), expected);
+    }
+
+    #[test]
+    fn test_euler_edge_cases() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 空图、只有孤立节点：空的欧拉回路
+        assert_eq!(graph.eulerian_circuit(), Some(Vec::new()));
+        graph.add_node("X", ());
+        assert_eq!(graph.eulerian_path(), Some(Vec::new()));
+
+        // 自环本身就是回路，孤立节点不影响
+        graph.add_node_and_edge("A", (), "A", ());
+        let a = *graph.get_idx_by_name("A").unwrap();
+        assert_eq!(graph.eulerian_circuit(), Some(vec![(a, a)]));
+
+        // 再加一条不相连的 B -> C：度数满足路径的条件，但不连通
+        graph.add_node_and_edge("B", (), "C", ());
+        assert!(!graph.has_eulerian_path());
+        // 出度比入度多 2 的节点
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("A", (), "B", ());
+        graph.add_node_and_edge("A", (), "C", ());
+        assert_eq!(graph.eulerian_path(), None);
+    }
+}
@@ -0,0 +1,384 @@
+/*
+网络流，边的容量由调用方给出：capacity(src_idx, dst_idx)，需非负
+- max_flow：Dinic 算法，BFS 分层后在层次图上反复找增广路，O(n²m)
+  找增广路用显式的栈，不递归，长链上也不会栈溢出
+- 返回 MaxFlow：最大流量、每条边上的流量、最小割（源点一侧的节点和割边）
+- min_cost_max_flow：在最大流的前提下使总费用最小，费用由 cost(src_idx, dst_idx) 给出，
+  每次沿残量网络中费用最小的路径增广（用 SPFA 找，允许负费用）
+  从源点可达的负费用环会让增广永不结束，此时返回 Error::NegativeCycle，存放残量网络中环上的节点 index
+  费用按 EPS 比较，浮点误差不会被当成负环
+  可以用来做带权的指派问题
+- 容量是 f64，残量小于 EPS 的边视为已经满载，避免浮点误差导致无休止的微小增广
+- 自环不会承载流量；src_idx == dst_idx 时流量为 0
+- max_flow_by_name、min_cost_max_flow_by_name：按节点名指定源点和汇点，名字不存在时返回 Error::UnknownName
+max_flow、min_cost_max_flow 只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用；按节点名的版本只在 Graph 上
+*/
+
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use crate::error::{Error, Result};
+use super::adjacency::{self, Adjacency};
+use super::bellman_ford::find_cycle;
+use super::Graph;
+
+
+const EPS: f64 = 1e-9;
+
+pub struct MaxFlow {
+    pub value: f64,
+    // 每条边上的流量 (src_idx, dst_idx, flow)，顺序与 get_all_edges 一致
+    pub flows: Vec<(usize, usize, f64)>,
+    // 最小割中源点一侧的节点（残量网络中从源点可达的节点），从小到大排列
+    pub source_side: Vec<usize>,
+    // 最小割的割边：从源点一侧指向另一侧的边，容量之和等于最大流量
+    pub cut_edges: Vec<(usize, usize)>,
+}
+
+
+// 残量网络。原图的第 i 条边对应 2i（正向，初始为容量）和 2i + 1（反向，初始为 0）
+struct Residual {
+    edges: Vec<(usize, usize)>,
+    to: Vec<usize>,
+    cap: Vec<f64>,
+    cost: Vec<f64>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl Residual {
+    fn new(n: usize, edges: Vec<(usize, usize)>, capacity: impl Fn(usize, usize) -> f64, cost: impl Fn(usize, usize) -> f64) -> Self {
+        let mut residual = Self { edges: Vec::new(), to: Vec::new(), cap: Vec::new(), cost: Vec::new(), adj: vec![Vec::new(); n] };
+        for (src_idx, dst_idx) in edges {
+            let (c, w) = if src_idx == dst_idx { (0.0, 0.0) } else { (capacity(src_idx, dst_idx), cost(src_idx, dst_idx)) };
+            residual.adj[src_idx].push(residual.to.len());
+            residual.to.push(dst_idx);
+            residual.cap.push(c);
+            residual.cost.push(w);
+            residual.adj[dst_idx].push(residual.to.len());
+            residual.to.push(src_idx);
+            residual.cap.push(0.0);
+            residual.cost.push(-w);
+            residual.edges.push((src_idx, dst_idx));
+        }
+        residual
+    }
+
+    // 沿边 e 推送 f 的流量
+    fn push(&mut self, e: usize, f: f64) {
+        self.cap[e] -= f;
+        self.cap[e ^ 1] += f;
+    }
+
+    // 每个节点在残量网络中离 src_idx 的层数，不可达为 usize::MAX
+    fn levels(&self, src_idx: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.adj.len()];
+        level[src_idx] = 0;
+        let mut q = VecDeque::from([src_idx]);
+        while let Some(idx) = q.pop_front() {
+            for &e in &self.adj[idx] {
+                if self.cap[e] > EPS && level[self.to[e]] == usize::MAX {
+                    level[self.to[e]] = level[idx] + 1;
+                    q.push_back(self.to[e]);
+                }
+            }
+        }
+        level
+    }
+
+    // SPFA 的 prev_edge 转为每个节点的上一个节点，没有记录的为 None
+    fn prev_nodes(&self, prev_edge: &[usize]) -> Vec<Option<usize>> {
+        prev_edge.iter().map(|&e| (e != usize::MAX).then(|| self.to[e ^ 1])).collect()
+    }
+
+    fn into_max_flow(self, src_idx: usize, value: f64) -> MaxFlow {
+        let level = self.levels(src_idx);
+        let in_source_side = |idx: usize| level[idx] != usize::MAX;
+        let flows = self.edges.iter().enumerate()
+            .map(|(i, &(src_idx, dst_idx))| (src_idx, dst_idx, self.cap[2 * i + 1]))
+            .collect();
+        let source_side = (0..level.len()).filter(|&idx| in_source_side(idx)).collect();
+        let cut_edges = self.edges.iter().copied()
+            .filter(|&(src_idx, dst_idx)| in_source_side(src_idx) && !in_source_side(dst_idx))
+            .collect();
+        MaxFlow { value, flows, source_side, cut_edges }
+    }
+}
+
+
+pub(super) fn max_flow<G: Adjacency + ?Sized>(graph: &G, src_idx: usize, dst_idx: usize, capacity: impl Fn(usize, usize) -> f64) -> Result<MaxFlow> {
+    adjacency::check_idx(graph, src_idx)?;
+    adjacency::check_idx(graph, dst_idx)?;
+    let mut residual = Residual::new(graph.node_count(), graph.get_all_edges(), capacity, |_, _| 0.0);
+    let mut value = 0.0;
+    if src_idx == dst_idx {
+        return Ok(residual.into_max_flow(src_idx, value));
+    }
+
+    loop {
+        let level = residual.levels(src_idx);
+        if level[dst_idx] == usize::MAX {
+            break;
+        }
+        // it[idx]：idx 的出边中下一条要尝试的边，试过走不通的边不再重复尝试
+        let mut it = vec![0; residual.adj.len()];
+        // 从源点出发的路径（边的编号），走到汇点就增广，走进死胡同就退回一步
+        let mut path: Vec<usize> = Vec::new();
+        let mut idx = src_idx;
+        loop {
+            if idx == dst_idx {
+                let f = path.iter().map(|&e| residual.cap[e]).fold(f64::INFINITY, f64::min);
+                for &e in &path {
+                    residual.push(e, f);
+                }
+                value += f;
+                path.clear();
+                idx = src_idx;
+                continue;
+            }
+            let next = residual.adj[idx][it[idx]..].iter()
+                .position(|&e| residual.cap[e] > EPS && level[residual.to[e]] == level[idx] + 1);
+            match next {
+                Some(offset) => {
+                    it[idx] += offset;
+                    let e = residual.adj[idx][it[idx]];
+                    path.push(e);
+                    idx = residual.to[e];
+                }
+                None => {
+                    it[idx] = residual.adj[idx].len();
+                    match path.pop() {
+                        // 退回上一个节点，跳过这条走不通的边
+                        Some(e) => {
+                            idx = residual.to[e ^ 1];
+                            it[idx] += 1;
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+    Ok(residual.into_max_flow(src_idx, value))
+}
+
+// 返回 (最大流, 总费用)
+pub(super) fn min_cost_max_flow<G: Adjacency + ?Sized>(
+    graph: &G,
+    src_idx: usize,
+    dst_idx: usize,
+    capacity: impl Fn(usize, usize) -> f64,
+    cost: impl Fn(usize, usize) -> f64,
+) -> Result<(MaxFlow, f64)> {
+    adjacency::check_idx(graph, src_idx)?;
+    adjacency::check_idx(graph, dst_idx)?;
+    let n = graph.node_count();
+    let mut residual = Residual::new(n, graph.get_all_edges(), capacity, cost);
+    let (mut value, mut total_cost) = (0.0, 0.0);
+    if src_idx == dst_idx {
+        return Ok((residual.into_max_flow(src_idx, value), total_cost));
+    }
+
+    loop {
+        // SPFA 找费用最小的增广路，prev_edge 记录到达每个节点的边
+        let mut dist = vec![f64::INFINITY; n];
+        let mut prev_edge = vec![usize::MAX; n];
+        let mut in_queue = vec![false; n];
+        // 当前最短路径包含的边数，达到 n 说明路径上有负费用的环，再增广下去不会结束
+        let mut path_len = vec![0; n];
+        let mut q = VecDeque::from([src_idx]);
+        dist[src_idx] = 0.0;
+        while let Some(idx) = q.pop_front() {
+            in_queue[idx] = false;
+            for &e in &residual.adj[idx] {
+                let next_idx = residual.to[e];
+                // 费用也按 EPS 比较：(d + w) - w 可能略小于 d，不算更短，否则会把浮点误差当成负环
+                if residual.cap[e] > EPS && dist[idx] + residual.cost[e] < dist[next_idx] - EPS {
+                    dist[next_idx] = dist[idx] + residual.cost[e];
+                    prev_edge[next_idx] = e;
+                    path_len[next_idx] = path_len[idx] + 1;
+                    // 沿 prev_edge 往回走能绕回来，说明确实有负费用的环；绕不回来时 prev_edge 还在变化，继续松弛
+                    if path_len[next_idx] >= n {
+                        if let Some(cycle) = find_cycle(&residual.prev_nodes(&prev_edge), next_idx) {
+                            return Err(Error::NegativeCycle(cycle));
+                        }
+                    }
+                    if !in_queue[next_idx] {
+                        in_queue[next_idx] = true;
+                        q.push_back(next_idx);
+                    }
+                }
+            }
+        }
+        if dist[dst_idx].is_infinite() {
+            break;
+        }
+
+        // 增广路最多 n - 1 条边，超过说明 prev_edge 中有环
+        let mut path = Vec::new();
+        let mut idx = dst_idx;
+        while idx != src_idx {
+            if path.len() >= n {
+                let prev = residual.prev_nodes(&prev_edge);
+                return Err(Error::NegativeCycle(find_cycle(&prev, dst_idx).unwrap_or_else(|| path.iter().map(|&e| residual.to[e]).collect())));
+            }
+            path.push(prev_edge[idx]);
+            idx = residual.to[prev_edge[idx] ^ 1];
+        }
+        let f = path.iter().map(|&e| residual.cap[e]).fold(f64::INFINITY, f64::min);
+        for &e in &path {
+            residual.push(e, f);
+        }
+        value += f;
+        total_cost += f * dist[dst_idx];
+    }
+    Ok((residual.into_max_flow(src_idx, value), total_cost))
+}
+
+
+impl<'a, K: Hash + Eq, T> Graph<'a, K, T> {
+    // 按节点名指定源点和汇点
+    pub fn max_flow_by_name<Q>(&self, src_name: &Q, dst_name: &Q, capacity: impl Fn(usize, usize) -> f64) -> Result<MaxFlow>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let src_idx = self.try_get_idx_by_name(src_name)?;
+        let dst_idx = self.try_get_idx_by_name(dst_name)?;
+        self.max_flow(src_idx, dst_idx, capacity)
+    }
+
+    pub fn min_cost_max_flow_by_name<Q>(&self, src_name: &Q, dst_name: &Q, capacity: impl Fn(usize, usize) -> f64, cost: impl Fn(usize, usize) -> f64) -> Result<(MaxFlow, f64)>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let src_idx = self.try_get_idx_by_name(src_name)?;
+        let dst_idx = self.try_get_idx_by_name(dst_name)?;
+        self.min_cost_max_flow(src_idx, dst_idx, capacity, cost)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+    use crate::Rng;
+
+    #[test]
+    fn test_max_flow() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("S", "V1", 16.0), ("S", "V2", 13.0), ("V2", "V1", 4.0), ("V1", "V3", 12.0), ("V3", "V2", 9.0),
+            ("V2", "V4", 14.0), ("V4", "V3", 7.0), ("V3", "T", 20.0), ("V4", "T", 4.0)];
+        let mut capacities = HashMap::new();
+        for (src_name, dst_name, c) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+            capacities.insert((*graph.get_idx_by_name(src_name).unwrap(), *graph.get_idx_by_name(dst_name).unwrap()), c);
+        }
+        let capacity = |src_idx, dst_idx| capacities[&(src_idx, dst_idx)];
+        let s = *graph.get_idx_by_name("S").unwrap();
+        let t = *graph.get_idx_by_name("T").unwrap();
+
+        let flow = graph.max_flow(s, t, capacity).unwrap();
+        assert_eq!(flow.value, 23.0);
+        // 流量守恒，且不超过容量
+        let mut balance = [0.0; 6];
+        for &(src_idx, dst_idx, f) in &flow.flows {
+            assert!(f <= capacity(src_idx, dst_idx));
+            balance[src_idx] -= f;
+            balance[dst_idx] += f;
+        }
+        assert_eq!(balance[t], 23.0);
+        assert!((0..6).filter(|&idx| idx != s && idx != t).all(|idx| balance[idx] == 0.0));
+        // 最小割的容量等于最大流
+        let cut: f64 = flow.cut_edges.iter().map(|&(src_idx, dst_idx)| capacity(src_idx, dst_idx)).sum();
+        assert_eq!(cut, 23.0);
+        assert_eq!(graph.get_names_by_idxes(&flow.source_side), vec![Some(&"S"), Some(&"V1"), Some(&"V2"), Some(&"V4")]);
+        assert!(graph.max_flow(s, 100, capacity).is_err());
+        assert_eq!(graph.max_flow_by_name("S", "T", capacity).unwrap().value, 23.0);
+        assert!(graph.max_flow_by_name("X", "T", capacity).is_err());
+
+        // 冻结后结果相同
+        let frozen = arena_list.freeze();
+        let frozen_flow = frozen.max_flow(s, t, capacity).unwrap();
+        assert_eq!(frozen_flow.value, 23.0);
+        assert_eq!(frozen_flow.flows, flow.flows);
+        assert_eq!(frozen_flow.source_side, flow.source_side);
+        assert_eq!(frozen.min_cost_max_flow(s, t, capacity, |_, _| 1.0).unwrap().0.value, 23.0);
+    }
+
+    #[test]
+    fn test_min_cost_max_flow() {
+        // 指派问题：两名工人、两项任务，每条边容量为 1，费用为完成该任务的代价
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let costs = vec![("W1", "J1", 4.0), ("W1", "J2", 1.0), ("W2", "J1", 2.0), ("W2", "J2", 3.0)];
+        for &(worker, job, _) in &costs {
+            graph.add_node_and_edge("S", (), worker, ());
+            graph.add_node_and_edge(worker, (), job, ());
+            graph.add_node_and_edge(job, (), "T", ());
+        }
+        let cost_map: HashMap<(usize, usize), f64> = costs.iter()
+            .map(|&(worker, job, c)| ((*graph.get_idx_by_name(worker).unwrap(), *graph.get_idx_by_name(job).unwrap()), c))
+            .collect();
+        let cost = |src_idx, dst_idx| cost_map.get(&(src_idx, dst_idx)).copied().unwrap_or(0.0);
+        let s = *graph.get_idx_by_name("S").unwrap();
+        let t = *graph.get_idx_by_name("T").unwrap();
+
+        let (flow, total_cost) = graph.min_cost_max_flow_by_name("S", "T", |_, _| 1.0, cost).unwrap();
+        assert_eq!(flow.value, 2.0);
+        assert_eq!(total_cost, 3.0);
+        let assigned: Vec<(usize, usize)> = flow.flows.iter()
+            .filter(|&&(src_idx, dst_idx, f)| f > 0.0 && cost_map.contains_key(&(src_idx, dst_idx)))
+            .map(|&(src_idx, dst_idx, _)| (src_idx, dst_idx))
+            .collect();
+        assert_eq!(assigned.len(), 2);
+        assert!(assigned.contains(&(*graph.get_idx_by_name("W1").unwrap(), *graph.get_idx_by_name("J2").unwrap())));
+        assert_eq!(graph.min_cost_max_flow(s, t, |_, _| 1.0, cost).unwrap().1, 3.0);
+        assert!(graph.min_cost_max_flow_by_name("S", "X", |_, _| 1.0, cost).is_err());
+    }
+
+    #[test]
+    fn test_min_cost_negative_cycle() {
+        // S -> A -> T，A、B 之间来回都是负费用，增广路会一直绕圈
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        for (src_name, dst_name) in [("S", "A"), ("A", "B"), ("B", "A"), ("A", "T")] {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let [a, b] = ["A", "B"].map(|name| *graph.get_idx_by_name(name).unwrap());
+        let s = *graph.get_idx_by_name("S").unwrap();
+        let t = *graph.get_idx_by_name("T").unwrap();
+        let cost = |src_idx, dst_idx| if (src_idx, dst_idx) == (a, b) || (src_idx, dst_idx) == (b, a) { -1.0 } else { 1.0 };
+        let Err(err) = graph.min_cost_max_flow(s, t, |_, _| 1.0, cost) else { panic!("negative cycle not detected") };
+        let mut cycle = err.cycle().unwrap().to_vec();
+        cycle.sort();
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(cycle, expected);
+    }
+
+    #[test]
+    fn test_min_cost_fractional() {
+        // 小数费用、0.3 的倍数作为容量，浮点误差不能被当成负环，也不能让增广路的回溯绕圈
+        let mut rng = Rng::new(1);
+        for _ in 0..3000 {
+            let n = 2 + rng.gen_range(7);
+            let mut arena_list = ArenaList::new();
+            let mut graph = Graph::new(&mut arena_list);
+            for i in 0..n {
+                graph.add_node(i, ());
+            }
+            let mut edges = HashMap::new();
+            for _ in 0..rng.gen_range(3 * n) {
+                let (src_idx, dst_idx) = (rng.gen_range(n), rng.gen_range(n));
+                graph.add_edge(src_idx, dst_idx);
+                let c = (1 + rng.gen_range(10)) as f64 * 0.3;
+                let w = (1 + rng.gen_range(500)) as f64 / 100.0;
+                edges.insert((src_idx, dst_idx), (c, w));
+            }
+            let capacity = |src_idx, dst_idx| edges[&(src_idx, dst_idx)].0;
+            let cost = |src_idx, dst_idx| edges[&(src_idx, dst_idx)].1;
+            let (flow, total_cost) = graph.min_cost_max_flow(0, n - 1, capacity, cost).unwrap();
+            assert!((flow.value - graph.max_flow(0, n - 1, capacity).unwrap().value).abs() < 1e-6);
+            let expected: f64 = flow.flows.iter().map(|&(src_idx, dst_idx, f)| f * cost(src_idx, dst_idx)).sum();
+            assert!((total_cost - expected).abs() < 1e-6);
+        }
+    }
+}
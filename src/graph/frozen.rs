@@ -0,0 +1,176 @@
+/*
+冻结后的只读图，边用 CSR（Compressed Sparse Row）存放
+- 节点 i 的下游为 next_targets[next_offsets[i]..next_offsets[i + 1]]，上游同理，都已排序
+- 相比每个节点两个 HashSet，遍历时是连续内存，也省去了 HashSet 的额外开销
+- 节点的 index、节点名与冻结前一致，nodes_names 直接沿用
+
+ArenaList::freeze 转为 FrozenGraph，FrozenGraph::thaw 转回可修改的 ArenaList，都是 O(n + m)
+只依赖拓扑结构的算法都在 Adjacency 上，调用前需要 use graph::Adjacency
+*/
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::Hash;
+use super::key_index::KeyIndex;
+use super::{Adjacency, ArenaList, Node};
+
+
+pub struct FrozenGraph<K, T> {
+    nodes_names: KeyIndex,
+    names: Vec<Option<K>>,
+    data: Vec<T>,
+    next_offsets: Vec<usize>,
+    next_targets: Vec<usize>,
+    prev_offsets: Vec<usize>,
+    prev_targets: Vec<usize>,
+}
+
+
+// 把每个节点的 HashSet 邻接表拼接为 CSR，返回 (offsets, targets)
+fn to_csr<'b>(adjacency: impl ExactSizeIterator<Item = &'b HashSet<usize>>) -> (Vec<usize>, Vec<usize>) {
+    let mut offsets = Vec::with_capacity(adjacency.len() + 1);
+    let mut targets = Vec::new();
+    offsets.push(0);
+    for set in adjacency {
+        let start = targets.len();
+        targets.extend(set.iter().copied());
+        targets[start..].sort_unstable();
+        offsets.push(targets.len());
+    }
+    (offsets, targets)
+}
+
+
+impl<K, T> ArenaList<K, T> {
+    // 冻结为只读的 CSR 图
+    pub fn freeze(self) -> FrozenGraph<K, T> {
+        let (next_offsets, next_targets) = to_csr(self.nodes.iter().map(|node| &node.next_idx));
+        let (prev_offsets, prev_targets) = to_csr(self.nodes.iter().map(|node| &node.prev_idx));
+        let (names, data) = self.nodes.into_iter().map(|node| (node.name, node.data)).unzip();
+        FrozenGraph {
+            nodes_names: self.nodes_names,
+            names,
+            data,
+            next_offsets,
+            next_targets,
+            prev_offsets,
+            prev_targets,
+        }
+    }
+}
+
+
+impl<K, T> FrozenGraph<K, T> {
+    // 解冻为可修改的 ArenaList
+    pub fn thaw(self) -> ArenaList<K, T> {
+        let nodes = self.names.into_iter().zip(self.data).enumerate()
+            .map(|(idx, (name, data))| Node {
+                name,
+                data,
+                next_idx: self.next_targets[self.next_offsets[idx]..self.next_offsets[idx + 1]].iter().copied().collect(),
+                prev_idx: self.prev_targets[self.prev_offsets[idx]..self.prev_offsets[idx + 1]].iter().copied().collect(),
+            }).collect();
+        ArenaList {
+            nodes_names: self.nodes_names,
+            nodes,
+        }
+    }
+
+    // 匿名节点返回 None
+    pub fn get_name_by_idx(&self, idx: usize) -> Option<&K> {
+        self.names[idx].as_ref()
+    }
+
+    pub fn get_data_by_idx(&self, idx: usize) -> &T {
+        &self.data[idx]
+    }
+
+    // 节点的下游，已排序
+    pub fn next_slice(&self, idx: usize) -> &[usize] {
+        &self.next_targets[self.next_offsets[idx]..self.next_offsets[idx + 1]]
+    }
+
+    // 节点的上游，已排序
+    pub fn prev_slice(&self, idx: usize) -> &[usize] {
+        &self.prev_targets[self.prev_offsets[idx]..self.prev_offsets[idx + 1]]
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.next_targets.len()
+    }
+}
+
+impl<K: Hash + Eq, T> FrozenGraph<K, T> {
+    pub fn get_idx_by_name<Q>(&self, name: &Q) -> Option<&usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes_names.get(&self.names, name)
+    }
+}
+
+impl<K, T> Adjacency for FrozenGraph<K, T> {
+    fn node_count(&self) -> usize { self.names.len() }
+
+    fn successors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.next_slice(idx).iter().copied()
+    }
+
+    fn predecessors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.prev_slice(idx).iter().copied()
+    }
+
+    // 下游已排序，可以二分查找
+    fn has_edge(&self, src_idx: usize, dst_idx: usize) -> bool {
+        self.next_slice(src_idx).binary_search(&dst_idx).is_ok()
+    }
+
+    // 下游已排序，按节点顺序拼接即可
+    fn get_all_edges(&self) -> Vec<(usize, usize)> {
+        (0..self.names.len())
+            .flat_map(|src_idx| self.next_slice(src_idx).iter().map(move |&dst_idx| (src_idx, dst_idx)))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_freeze_thaw() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("John", "Emma"), ("Sophia", "Tom"), ("Tom", "John"), ("Tom", "Sophia"), ("Emma", "Lucy"), ("Tom", "Lucy")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let downstream = graph.get_downstream(vec![2], 100);
+        let shortest = graph.get_shortest(2, 4, 100);
+
+        let frozen = arena_list.freeze();
+        assert_eq!(frozen.node_count(), 5);
+        assert_eq!(frozen.edge_count(), 6);
+        assert_eq!(frozen.next_slice(3), &[0, 2, 4]);
+        assert_eq!(frozen.prev_slice(4), &[1, 3]);
+        assert!(frozen.has_edge(3, 2));
+        assert!(!frozen.has_edge(4, 3));
+        assert_eq!(frozen.get_idx_by_name("Tom"), Some(&3));
+        assert_eq!(frozen.get_name_by_idx(4), Some(&"Lucy"));
+
+        // 冻结前后，算法结果一致（同一层内的顺序可能不同）
+        let mut frozen_downstream = frozen.get_downstream(vec![2], 100);
+        for (level, idxes) in downstream {
+            let mut idxes = idxes;
+            idxes.sort();
+            frozen_downstream.get_mut(&level).unwrap().sort();
+            assert_eq!(frozen_downstream[&level], idxes);
+        }
+        assert_eq!(frozen.get_shortest(2, 4, 100), shortest);
+
+        let mut arena_list = frozen.thaw();
+        let mut graph = Graph::new(&mut arena_list);
+        assert_eq!(graph.get_all_edges().len(), 6);
+        assert!(graph.del_node_by_name("Tom"));
+        assert_eq!(graph.get_shortest(*graph.get_idx_by_name("John").unwrap(), *graph.get_idx_by_name("Lucy").unwrap(), 100), Some(2));
+    }
+}
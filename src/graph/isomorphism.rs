@@ -0,0 +1,454 @@
+/*
+VF2 风格的图同构 / 子图同构匹配，self 为模式图（pattern），参数 target 为目标图
+- MatchMode::Isomorphism：两个图同构，节点一一对应，边也一一对应
+- MatchMode::InducedSubgraph：模式图与目标图的一个导出子图同构（映射到的节点之间，边必须完全一致）
+- MatchMode::Monomorphism：模式图的每条边在目标图中都存在，目标图中可以有多余的边，适合找 motif
+- node_match(&pattern_data, &target_data)：节点数据是否匹配，不关心时传入 |_, _| true
+- edge_match((p_src, p_dst), (t_src, t_dst))：边是否匹配，参数为两图中的节点 index，可以据此查边权
+- limit：最多返回多少个映射，None 返回全部
+返回的每个映射为 Vec<usize>，第 i 个元素是模式图第 i 个节点对应的目标图节点 index
+
+实现
+- 模式图节点按 BFS 顺序（从度数大的节点开始）依次匹配，候选节点取已匹配邻居的像的邻居，尽早剪枝
+- VF2 的前瞻规则：T_out / T_in 为未匹配、但有已匹配的上游 / 下游的节点，其余未匹配的节点为 N
+  候选对 (u, t) 的未匹配上游、下游分别落在 T_in、T_out、N 中的个数，模式图一侧与目标图一侧比较
+  同构要求相等；导出子图要求不多于；单射（Monomorphism）只比较 T_in、T_out，N 中的节点之后可以映射到 T 中
+- 用显式的栈回溯，不递归
+- node_match 要读节点数据，不写在 Adjacency 上；Graph 与 Graph、FrozenGraph 与 FrozenGraph 之间各有一个 vf2_matches，共用同一份实现
+*/
+
+use super::adjacency::Adjacency;
+use super::{FrozenGraph, Graph};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    Isomorphism,
+    InducedSubgraph,
+    Monomorphism,
+}
+
+
+// 更新节点 idx 加入（add 为 true）或移出映射后，各节点的 in_cnt / out_cnt：
+// 有多少个已匹配的节点是它的下游 / 上游。计数大于 0 且未匹配的节点属于 T_in / T_out
+fn update_terminal<G: Adjacency + ?Sized>(graph: &G, idx: usize, in_cnt: &mut [usize], out_cnt: &mut [usize], add: bool) {
+    for next_idx in graph.successors(idx) {
+        out_cnt[next_idx] = if add { out_cnt[next_idx] + 1 } else { out_cnt[next_idx] - 1 };
+    }
+    for prev_idx in graph.predecessors(idx) {
+        in_cnt[prev_idx] = if add { in_cnt[prev_idx] + 1 } else { in_cnt[prev_idx] - 1 };
+    }
+}
+
+// 节点 idx 的未匹配上游、下游（不含自身）分别落在 T_in、T_out、N 中的个数：
+// [上游∩T_in, 上游∩T_out, 上游∩N, 下游∩T_in, 下游∩T_out, 下游∩N]
+fn lookahead<G: Adjacency + ?Sized>(graph: &G, idx: usize, is_mapped: impl Fn(usize) -> bool, in_cnt: &[usize], out_cnt: &[usize]) -> [usize; 6] {
+    let mut res = [0; 6];
+    let prev: Vec<usize> = graph.predecessors(idx).collect();
+    let next: Vec<usize> = graph.successors(idx).collect();
+    for (offset, neighbors) in [(0, prev), (3, next)] {
+        for x in neighbors {
+            if x == idx || is_mapped(x) {
+                continue;
+            }
+            if in_cnt[x] > 0 {
+                res[offset] += 1;
+            }
+            if out_cnt[x] > 0 {
+                res[offset + 1] += 1;
+            }
+            if in_cnt[x] == 0 && out_cnt[x] == 0 {
+                res[offset + 2] += 1;
+            }
+        }
+    }
+    res
+}
+
+
+// 模式图节点的匹配顺序：每个连通分量从度数最大的节点出发 BFS，把边看作无向边
+fn match_order<G: Adjacency + ?Sized>(graph: &G, out_degree: &[usize], in_degree: &[usize]) -> Vec<usize> {
+    let n = graph.node_count();
+    let degree = |idx: usize| out_degree[idx] + in_degree[idx];
+    let mut roots: Vec<usize> = (0..n).collect();
+    roots.sort_by_key(|&idx| (usize::MAX - degree(idx), idx));
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for root in roots {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let start = order.len();
+        order.push(root);
+        let mut i = start;
+        while i < order.len() {
+            let idx = order[i];
+            let mut neighbors: Vec<usize> = graph.successors(idx).chain(graph.predecessors(idx))
+                .filter(|&next_idx| !visited[next_idx])
+                .collect();
+            neighbors.sort_by_key(|&next_idx| (usize::MAX - degree(next_idx), next_idx));
+            neighbors.dedup();
+            for next_idx in neighbors {
+                visited[next_idx] = true;
+                order.push(next_idx);
+            }
+            i += 1;
+        }
+    }
+    order
+}
+
+
+// 只依赖拓扑结构的部分，node_match 接收两图中的节点 index；Graph 和 FrozenGraph 的 vf2_matches 把节点数据传给调用方的 node_match
+fn vf2_matches<P: Adjacency + ?Sized, G: Adjacency + ?Sized>(
+    pattern: &P,
+    target: &G,
+    mode: MatchMode,
+    node_match: impl Fn(usize, usize) -> bool,
+    edge_match: impl Fn((usize, usize), (usize, usize)) -> bool,
+    limit: Option<usize>,
+) -> Vec<Vec<usize>> {
+    let (p_n, t_n) = (pattern.node_count(), target.node_count());
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut res = Vec::new();
+    if limit == 0 {
+        return res;
+    }
+    let p_out_degree: Vec<usize> = (0..p_n).map(|idx| pattern.successors(idx).count()).collect();
+    let p_in_degree: Vec<usize> = (0..p_n).map(|idx| pattern.predecessors(idx).count()).collect();
+    let t_out_degree: Vec<usize> = (0..t_n).map(|idx| target.successors(idx).count()).collect();
+    let t_in_degree: Vec<usize> = (0..t_n).map(|idx| target.predecessors(idx).count()).collect();
+    if mode == MatchMode::Isomorphism
+        && (p_n != t_n || p_out_degree.iter().sum::<usize>() != t_out_degree.iter().sum::<usize>()) {
+        return res;
+    }
+    if p_n > t_n {
+        return res;
+    }
+    if p_n == 0 {
+        res.push(Vec::new());
+        return res;
+    }
+
+    let order = match_order(pattern, &p_out_degree, &p_in_degree);
+    let mut mapping: Vec<Option<usize>> = vec![None; p_n];
+    let mut reverse: Vec<Option<usize>> = vec![None; t_n];
+    let (mut p_in, mut p_out) = (vec![0; p_n], vec![0; p_n]);
+    let (mut t_in, mut t_out) = (vec![0; t_n], vec![0; t_n]);
+
+    // 候选节点：已匹配的邻居在目标图中的像的邻居；没有已匹配的邻居时为全部节点
+    let candidates = |u: usize, mapping: &[Option<usize>]| -> Vec<usize> {
+        let mut cands: Vec<usize> = if let Some(t) = pattern.predecessors(u).find_map(|p| mapping[p]) {
+            target.successors(t).collect()
+        } else if let Some(t) = pattern.successors(u).find_map(|p| mapping[p]) {
+            target.predecessors(t).collect()
+        } else {
+            (0..t_n).collect()
+        };
+        cands.sort_unstable();
+        cands
+    };
+
+    // 前瞻规则，见文件开头
+    let terminal_ok = |p_counts: [usize; 6], t_counts: [usize; 6]| -> bool {
+        match mode {
+            MatchMode::Isomorphism => p_counts == t_counts,
+            MatchMode::InducedSubgraph => p_counts.iter().zip(&t_counts).all(|(p, t)| p <= t),
+            MatchMode::Monomorphism => [0, 1, 3, 4].iter().all(|&i| p_counts[i] <= t_counts[i]),
+        }
+    };
+
+    let feasible = |u: usize, t: usize, mapping: &[Option<usize>], reverse: &[Option<usize>]| -> bool {
+        let degree_ok = match mode {
+            MatchMode::Isomorphism => p_out_degree[u] == t_out_degree[t] && p_in_degree[u] == t_in_degree[t],
+            _ => p_out_degree[u] <= t_out_degree[t] && p_in_degree[u] <= t_in_degree[t],
+        };
+        if reverse[t].is_some() || !degree_ok || !node_match(u, t) {
+            return false;
+        }
+        // 自环和已匹配节点之间的边：模式图中的边在目标图中必须存在
+        let image = |p: usize| if p == u { Some(t) } else { mapping[p] };
+        for p in pattern.successors(u) {
+            if let Some(t2) = image(p) {
+                if !target.has_edge(t, t2) || !edge_match((u, p), (t, t2)) {
+                    return false;
+                }
+            }
+        }
+        for p in pattern.predecessors(u) {
+            if let Some(t2) = image(p) {
+                if !target.has_edge(t2, t) || !edge_match((p, u), (t2, t)) {
+                    return false;
+                }
+            }
+        }
+        // 同构和导出子图还要求反过来：目标图中已匹配节点之间的边，模式图中也必须存在
+        if mode != MatchMode::Monomorphism {
+            let preimage = |t2: usize| if t2 == t { Some(u) } else { reverse[t2] };
+            if target.successors(t).any(|t2| preimage(t2).is_some_and(|p| !pattern.has_edge(u, p)))
+                || target.predecessors(t).any(|t2| preimage(t2).is_some_and(|p| !pattern.has_edge(p, u))) {
+                return false;
+            }
+        }
+        true
+    };
+
+    // stack[depth] = (order[depth] 的候选节点, 下一个要尝试的位置)
+    let mut stack = vec![(candidates(order[0], &mapping), 0)];
+    while !stack.is_empty() {
+        let depth = stack.len() - 1;
+        let u = order[depth];
+        let (cands, pos) = &mut stack[depth];
+        // 撤销这一层上一次的选择
+        if let Some(t) = mapping[u].take() {
+            reverse[t] = None;
+            update_terminal(pattern, u, &mut p_in, &mut p_out, false);
+            update_terminal(target, t, &mut t_in, &mut t_out, false);
+        }
+        if *pos >= cands.len() {
+            stack.pop();
+            continue;
+        }
+        let t = cands[*pos];
+        *pos += 1;
+        if !feasible(u, t, &mapping, &reverse) {
+            continue;
+        }
+        let p_counts = lookahead(pattern, u, |x| mapping[x].is_some(), &p_in, &p_out);
+        let t_counts = lookahead(target, t, |y| reverse[y].is_some(), &t_in, &t_out);
+        if !terminal_ok(p_counts, t_counts) {
+            continue;
+        }
+        mapping[u] = Some(t);
+        reverse[t] = Some(u);
+        update_terminal(pattern, u, &mut p_in, &mut p_out, true);
+        update_terminal(target, t, &mut t_in, &mut t_out, true);
+        if depth + 1 == order.len() {
+            res.push(mapping.iter().map(|t| t.unwrap()).collect());
+            if res.len() >= limit {
+                break;
+            }
+        } else {
+            let next_cands = candidates(order[depth + 1], &mapping);
+            stack.push((next_cands, 0));
+        }
+    }
+    res
+}
+
+
+impl<'a, K, T> Graph<'a, K, T> {
+    pub fn vf2_matches<K2, T2>(
+        &self,
+        target: &Graph<'_, K2, T2>,
+        mode: MatchMode,
+        node_match: impl Fn(&T, &T2) -> bool,
+        edge_match: impl Fn((usize, usize), (usize, usize)) -> bool,
+        limit: Option<usize>,
+    ) -> Vec<Vec<usize>> {
+        let (p_nodes, t_nodes) = (&self.owner.nodes, &target.owner.nodes);
+        vf2_matches(self, target, mode, |u, t| node_match(&p_nodes[u].data, &t_nodes[t].data), edge_match, limit)
+    }
+
+    pub fn is_isomorphic<K2, T2>(&self, other: &Graph<'_, K2, T2>) -> bool {
+        !self.vf2_matches(other, MatchMode::Isomorphism, |_, _| true, |_, _| true, Some(1)).is_empty()
+    }
+}
+
+impl<K, T> FrozenGraph<K, T> {
+    pub fn vf2_matches<K2, T2>(
+        &self,
+        target: &FrozenGraph<K2, T2>,
+        mode: MatchMode,
+        node_match: impl Fn(&T, &T2) -> bool,
+        edge_match: impl Fn((usize, usize), (usize, usize)) -> bool,
+        limit: Option<usize>,
+    ) -> Vec<Vec<usize>> {
+        vf2_matches(self, target, mode, |u, t| node_match(self.get_data_by_idx(u), target.get_data_by_idx(t)), edge_match, limit)
+    }
+
+    pub fn is_isomorphic<K2, T2>(&self, other: &FrozenGraph<K2, T2>) -> bool {
+        !self.vf2_matches(other, MatchMode::Isomorphism, |_, _| true, |_, _| true, Some(1)).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Rng;
+    use crate::graph::{Adjacency, ArenaList, Graph, MatchMode};
+
+    #[test]
+    fn test_vf2() {
+        // 模式：A->B, A->C, B->C
+        let mut pattern_arena_list = ArenaList::new();
+        let mut pattern = Graph::new(&mut pattern_arena_list);
+        for (src_name, dst_name) in [("A", "B"), ("A", "C"), ("B", "C")] {
+            pattern.add_node_and_edge(src_name, (), dst_name, ());
+        }
+
+        let mut target_arena_list = ArenaList::new();
+        let mut target = Graph::new(&mut target_arena_list);
+        let vec1 = vec![(1, 2), (1, 3), (2, 3), (3, 4), (2, 4), (4, 5), (5, 1), (3, 1)];
+        for (src_name, dst_name) in vec1 {
+            target.add_node_and_edge(src_name, src_name * 10, dst_name, dst_name * 10);
+        }
+        let name = |idx| *target.get_name_by_idx(idx).unwrap();
+
+        // 1->2->3、2->3->4 两个三角形
+        let mut motifs: Vec<Vec<i32>> = pattern.vf2_matches(&target, MatchMode::Monomorphism, |_, _| true, |_, _| true, None)
+            .into_iter()
+            .map(|mapping| mapping.into_iter().map(name).collect())
+            .collect();
+        motifs.sort();
+        assert_eq!(motifs, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+
+        // 1、3 之间还有 3->1，导出子图只剩 2->3->4
+        let induced = pattern.vf2_matches(&target, MatchMode::InducedSubgraph, |_, _| true, |_, _| true, None);
+        assert_eq!(induced.len(), 1);
+        assert_eq!(induced[0].iter().map(|&idx| name(idx)).collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        // 节点数据谓词、数量限制
+        let with_data = pattern.vf2_matches(&target, MatchMode::Monomorphism, |_, &data| data != 40, |_, _| true, None);
+        assert_eq!(with_data.len(), 1);
+        let first = pattern.vf2_matches(&target, MatchMode::Monomorphism, |_, _| true, |_, _| true, Some(1));
+        assert_eq!(first.len(), 1);
+
+        // 同构：换一套名字、换一种插入顺序
+        let mut other_arena_list = ArenaList::new();
+        let mut other = Graph::new(&mut other_arena_list);
+        for (src_name, dst_name) in [("Y", "Z"), ("X", "Z"), ("X", "Y")] {
+            other.add_node_and_edge(src_name.to_string(), (), dst_name.to_string(), ());
+        }
+        assert!(pattern.is_isomorphic(&other));
+        other.add_edge(0, 0);
+        assert!(!pattern.is_isomorphic(&other));
+
+        // 冻结后结果相同
+        let frozen_pattern = pattern_arena_list.freeze();
+        let frozen_target = target_arena_list.freeze();
+        assert_eq!(frozen_pattern.vf2_matches(&frozen_target, MatchMode::InducedSubgraph, |_, _| true, |_, _| true, None), induced);
+        assert_eq!(frozen_pattern.vf2_matches(&frozen_target, MatchMode::Monomorphism, |_, &data| data != 40, |_, _| true, None), with_data);
+        assert!(!frozen_pattern.is_isomorphic(&other_arena_list.freeze()));
+    }
+
+    #[test]
+    fn test_vf2_edge_cases() {
+        let mut empty_arena_list: ArenaList<&str, ()> = ArenaList::new();
+        let empty = Graph::new(&mut empty_arena_list);
+        let mut loop_arena_list = ArenaList::new();
+        let mut self_loop = Graph::new(&mut loop_arena_list);
+        self_loop.add_node_and_edge("L", (), "L", ());
+        // 三个孤立节点，其中一个有自环
+        let mut target_arena_list = ArenaList::new();
+        let mut target = Graph::new(&mut target_arena_list);
+        target.add_node(1, ());
+        target.add_node_and_edge(2, (), 2, ());
+        target.add_node(3, ());
+        let all = |pattern: &Graph<&str, ()>, target: &Graph<i32, ()>, mode| pattern.vf2_matches(target, mode, |_, _| true, |_, _| true, None);
+
+        // 空的模式图有且只有一个（空的）映射
+        assert_eq!(all(&empty, &target, MatchMode::Monomorphism), vec![Vec::<usize>::new()]);
+        assert!(all(&empty, &target, MatchMode::Isomorphism).is_empty());
+        assert!(empty.is_isomorphic(&empty));
+        // 自环只能映射到有自环的节点；模式图比目标图大时没有映射
+        assert_eq!(all(&self_loop, &target, MatchMode::Monomorphism), vec![vec![1]]);
+        assert!(target.vf2_matches(&self_loop, MatchMode::Monomorphism, |_, _| true, |_, _| true, None).is_empty());
+
+        // 不连通的模式图：两个孤立节点。单射有 6 个，导出子图要求两者都没有自环，只剩 2 个
+        let mut pair_arena_list = ArenaList::new();
+        let mut pair = Graph::new(&mut pair_arena_list);
+        pair.add_node("P", ());
+        pair.add_node("Q", ());
+        assert_eq!(all(&pair, &target, MatchMode::Monomorphism).len(), 6);
+        assert_eq!(all(&pair, &target, MatchMode::InducedSubgraph).len(), 2);
+        // 数量限制
+        assert!(pair.vf2_matches(&target, MatchMode::Monomorphism, |_, _| true, |_, _| true, Some(0)).is_empty());
+        assert_eq!(pair.vf2_matches(&target, MatchMode::Monomorphism, |_, _| true, |_, _| true, Some(4)).len(), 4);
+        // 边谓词拒绝所有边时，带自环的模式图没有映射
+        assert!(self_loop.vf2_matches(&target, MatchMode::Monomorphism, |_, _| true, |_, _| false, None).is_empty());
+    }
+
+    #[test]
+    fn test_vf2_random() {
+        // 随机小图，与枚举全部单射的结果对照
+        fn random_graph(arena_list: &mut ArenaList<usize, ()>, n: usize, m: usize, rng: &mut Rng) {
+            let mut graph = Graph::new(arena_list);
+            for i in 0..n {
+                graph.add_node(i, ());
+            }
+            for _ in 0..m {
+                graph.add_edge(rng.gen_range(n), rng.gen_range(n));
+            }
+        }
+        // 长度为 k、元素取自 0..n 且互不相同的全部序列
+        fn injections(k: usize, n: usize) -> Vec<Vec<usize>> {
+            let mut res: Vec<Vec<usize>> = vec![Vec::new()];
+            for _ in 0..k {
+                let mut longer = Vec::new();
+                for prefix in &res {
+                    for t in (0..n).filter(|t| !prefix.contains(t)) {
+                        let mut mapping = prefix.clone();
+                        mapping.push(t);
+                        longer.push(mapping);
+                    }
+                }
+                res = longer;
+            }
+            res
+        }
+
+        let mut rng = Rng::new(11);
+        for round in 0..30 {
+            let (p_n, t_n) = (1 + rng.gen_range(4), 3 + rng.gen_range(3));
+            let mut pattern_arena_list = ArenaList::new();
+            random_graph(&mut pattern_arena_list, p_n, rng.gen_range(6), &mut rng);
+            let mut target_arena_list = ArenaList::new();
+            random_graph(&mut target_arena_list, t_n, 2 + rng.gen_range(10), &mut rng);
+            let pattern = Graph::new(&mut pattern_arena_list);
+            let target = Graph::new(&mut target_arena_list);
+            let p_edges = pattern.get_all_edges();
+            let t_edges = target.get_all_edges();
+
+            let maps = injections(p_n, t_n);
+            let mono = |mapping: &Vec<usize>| p_edges.iter().all(|&(u, v)| t_edges.contains(&(mapping[u], mapping[v])));
+            let induced = |mapping: &Vec<usize>| (0..p_n).all(|u| (0..p_n).all(|v| p_edges.contains(&(u, v)) == t_edges.contains(&(mapping[u], mapping[v]))));
+            for (mode, check) in [(MatchMode::Monomorphism, &mono as &dyn Fn(&Vec<usize>) -> bool), (MatchMode::InducedSubgraph, &induced)] {
+                let mut expected: Vec<Vec<usize>> = maps.iter().filter(|mapping| check(mapping)).cloned().collect();
+                let mut found = pattern.vf2_matches(&target, mode, |_, _| true, |_, _| true, None);
+                expected.sort();
+                found.sort();
+                assert_eq!(found, expected, "round {} {:?}", round, mode);
+            }
+
+            // 目标图打乱节点顺序后与自身同构；多一条边后不同构
+            let perm = {
+                let mut perm: Vec<usize> = (0..t_n).collect();
+                rng.shuffle(&mut perm);
+                perm
+            };
+            let mut shuffled_arena_list = ArenaList::new();
+            let mut shuffled = Graph::new(&mut shuffled_arena_list);
+            for i in 0..t_n {
+                shuffled.add_node(i, ());
+            }
+            for &(src_idx, dst_idx) in &t_edges {
+                shuffled.add_edge(perm[src_idx], perm[dst_idx]);
+            }
+            assert!(target.is_isomorphic(&shuffled));
+            // 全部同构映射与穷举对照
+            let s_edges = shuffled.get_all_edges();
+            let mut expected: Vec<Vec<usize>> = injections(t_n, t_n).into_iter()
+                .filter(|mapping| (0..t_n).all(|u| (0..t_n).all(|v| t_edges.contains(&(u, v)) == s_edges.contains(&(mapping[u], mapping[v])))))
+                .collect();
+            let mut found = target.vf2_matches(&shuffled, MatchMode::Isomorphism, |_, _| true, |_, _| true, None);
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected, "round {}", round);
+            if let Some((src_idx, dst_idx)) = (0..t_n * t_n).map(|i| (i / t_n, i % t_n)).find(|edge| !t_edges.contains(edge)) {
+                shuffled.add_edge(perm[src_idx], perm[dst_idx]);
+                assert!(!target.is_isomorphic(&shuffled));
+            }
+        }
+    }
+}
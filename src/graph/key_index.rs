@@ -0,0 +1,122 @@
+/*
+节点名的索引，用于按节点名快速找到节点的 index
+
+节点名只在 Node::name 中存放一份，这里只存节点名的哈希值 -> index
+- slots: {hash: idx}，绝大多数节点都在这里
+- collisions: 哈希值与 slots 中已有节点相同、但节点名不同的节点 index。极少出现，线性查找
+查询时先比较哈希值，再回到 nodes 中比较节点名本身
+
+所有操作都需要传入 nodes，以便读取节点名。删除/移动节点时，要在 nodes 变动之前调用
+nodes 可以是 ArenaList 的 Vec<Node>，也可以是 FrozenGraph 的 Vec<Option<K>>，见 NameSlots
+*/
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher, RandomState};
+use super::Node;
+
+
+// 按 index 读取节点名
+pub(crate) trait NameSlots<K> {
+    fn name_at(&self, idx: usize) -> Option<&K>;
+}
+
+impl<K, T> NameSlots<K> for Vec<Node<K, T>> {
+    fn name_at(&self, idx: usize) -> Option<&K> { self[idx].name.as_ref() }
+}
+
+impl<K> NameSlots<K> for Vec<Option<K>> {
+    fn name_at(&self, idx: usize) -> Option<&K> { self[idx].as_ref() }
+}
+
+
+// slots 的 key 已经是哈希值，不需要再哈希一次
+#[derive(Default)]
+struct HashValueHasher(u64);
+
+impl Hasher for HashValueHasher {
+    fn finish(&self) -> u64 { self.0 }
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 << 8) | b as u64;
+        }
+    }
+    fn write_u64(&mut self, n: u64) { self.0 = n }
+}
+
+
+#[derive(Default)]
+pub(crate) struct KeyIndex {
+    hash_builder: RandomState,
+    slots: HashMap<u64, usize, BuildHasherDefault<HashValueHasher>>,
+    collisions: Vec<usize>,
+}
+
+impl KeyIndex {
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.slots.clear();
+        self.collisions.clear();
+    }
+
+    pub(crate) fn get<'s, K, Q, N>(&'s self, nodes: &N, key: &Q) -> Option<&'s usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized, N: NameSlots<K> {
+        let key_eq = |idx: usize| nodes.name_at(idx).is_some_and(|k| k.borrow() == key);
+        let idx = self.slots.get(&self.hash(key))?;
+        if key_eq(*idx) {
+            return Some(idx);
+        }
+        self.collisions.iter().find(|&&idx| key_eq(idx))
+    }
+
+    // 把 nodes[idx] 的节点名加入索引。调用方需保证该节点名尚不在索引中
+    pub(crate) fn insert<K: Hash, N: NameSlots<K>>(&mut self, nodes: &N, idx: usize) {
+        if let Some(key) = nodes.name_at(idx) {
+            let hash = self.hash(key);
+            match self.slots.entry(hash) {
+                Entry::Occupied(_) => self.collisions.push(idx),
+                Entry::Vacant(entry) => { entry.insert(idx); }
+            }
+        }
+    }
+
+    // 把 nodes[idx] 的节点名移出索引
+    pub(crate) fn remove<K: Hash, N: NameSlots<K>>(&mut self, nodes: &N, idx: usize) {
+        let hash = match nodes.name_at(idx) {
+            Some(key) => self.hash(key),
+            None => return,
+        };
+        if self.slots.get(&hash) == Some(&idx) {
+            self.slots.remove(&hash);
+            // 如果有同哈希值的冲突节点，挪一个到 slots 中
+            let promoted = self.collisions.iter()
+                .position(|&other| nodes.name_at(other).is_some_and(|k| self.hash(k) == hash));
+            if let Some(pos) = promoted {
+                let other = self.collisions.swap_remove(pos);
+                self.slots.insert(hash, other);
+            }
+        } else if let Some(pos) = self.collisions.iter().position(|&other| other == idx) {
+            self.collisions.swap_remove(pos);
+        }
+    }
+
+    // nodes[old_idx] 将被移动到 new_idx，更新其索引
+    pub(crate) fn relocate<K: Hash, N: NameSlots<K>>(&mut self, nodes: &N, old_idx: usize, new_idx: usize) {
+        let hash = match nodes.name_at(old_idx) {
+            Some(key) => self.hash(key),
+            None => return,
+        };
+        match self.slots.get_mut(&hash) {
+            Some(idx) if *idx == old_idx => *idx = new_idx,
+            _ => {
+                if let Some(idx) = self.collisions.iter_mut().find(|idx| **idx == old_idx) {
+                    *idx = new_idx;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,927 @@
+/*
+功能
+- 增
+    - 增加节点 add_node
+    - 增加边 add_edge
+- 查
+    - get_node_by_idx
+    - get_name_by_idx
+    - 匿名节点（add_anonymous_node）没有名字，get_name_by_idx 返回 None
+    - TODO: 根据各种条件做查询
+- 删
+    - 删除节点，del_node_by_idx，del_node_by_name，
+    - 删除边，del_edge_by_idx，del_edge_by_name
+    - clear:清空所有数据
+- 出错
+    - 每个可能失败的操作都有 try_ 版本，返回 Result<_, Error>，不会 panic
+- 存取
+    - save/load：与 GraphSnapshot 互转，开启 serde feature 后可序列化
+- 改
+    - 改节点对应的值：add_node
+    - 节点改名：rename_node_by_idx，rename_node_by_name，新名字已被占用时失败
+- 高级功能
+    - 遍历全部下游
+    - 寻找最近链路：get_shortest，双向 BFS
+    - ？寻找关键链接
+- 遍历
+    - dfs、bfs：事件（发现、树边、回边、前向/横叉边、结束）的迭代器，不递归
+    - dfs_visit、bfs_visit：visitor 返回 Control，可以剪掉子树或提前结束
+    - try_dfs、try_bfs、try_dfs_visit、try_bfs_visit：先检查起点，不存在时返回 Error::UnknownNode
+- 子图
+    - induced_subgraph、ego_graph：选中的节点和它们之间的边复制到新的 ArenaList，并返回新旧 index 的对应关系
+- 欧拉路径
+    - has_eulerian_circuit、has_eulerian_path，eulerian_circuit、eulerian_path（Hierholzer）返回边的序列
+- 着色与团
+    - dsatur_coloring、welsh_powell_coloring，exact_coloring 限制步数求精确解
+    - maximal_independent_set、maximal_cliques、maximum_clique（Bron-Kerbosch），_names 版本返回节点名
+- 同构
+    - vf2_matches：图同构、导出子图同构、子图单态（找 motif），可以按节点数据、边过滤
+    - is_isomorphic
+- 随机游走与采样
+    - random_walks、random_walk_names：node2vec 风格（p、q），可以带权、带重启
+    - sample_nodes、sample_edges、snowball_sample
+    - 随机数来自 crate::Rng（SplitMix64），由 seed 决定，可以复现
+- 排序
+    - pagerank、personalized_pagerank
+- 有向无环图
+    - topological_sort，有环时返回 Error::Cyclic
+    - transitive_closure：可达性索引 Reachability，O(1) 查询
+    - transitive_reduction（原地）、transitive_reduction_to_arena_list：删除多余的边
+- 中心性
+    - in_degree_centrality、out_degree_centrality、closeness_centrality、harmonic_centrality
+    - betweenness_centrality（Brandes），可以按 seed 随机抽样起点得到近似值
+- 社区发现
+    - label_propagation、louvain，把边看作无向边，可以带权；结果由 seed 决定，可以复现
+    - modularity：任意社区划分的模块度
+- 结构统计
+    - core_numbers、k_core，triangle_counts、triangle_count
+    - local_clustering、average_clustering、global_clustering，degree_histogram
+- 连通性
+    - weakly_connected_components、component_ids，基于并查集 UnionFind
+- 二分图
+    - bipartition：二染色，不是二分图时给出奇环
+    - max_matching（Hopcroft-Karp）、min_cost_assignment（匈牙利算法）
+- 带权算法
+    - 边权通过闭包 weight(src_idx, dst_idx) 给出，不存放在图中
+    - 最小生成树/森林：mst_kruskal、mst_prim
+    - A* 搜索：astar
+    - 含负权的单源最短路：bellman_ford、spfa，遇到负环返回 Error::NegativeCycle，get_cycle_names 转为节点名
+    - 前 k 条最短的简单路径：k_shortest_paths（Yen 算法），k_shortest_paths_by_name 以节点名返回路径
+    - 全源最短路：all_pairs_bfs（跳数）、floyd_warshall、johnson，返回 DistanceMatrix，可求离心率、直径、半径、中心
+    - 网络流：max_flow（Dinic，同时给出最小割）、min_cost_max_flow，容量、费用同样通过闭包给出，*_by_name 按节点名指定源点和汇点
+- 冻结
+    - ArenaList::freeze 转为只读的 FrozenGraph（CSR 存边），遍历更快、更省内存；FrozenGraph::thaw 转回来
+    - 只依赖拓扑结构的算法写在 Adjacency 上，两种图都可以使用，调用前需要 use graph::Adjacency：
+      get_all_edges、has_edge、get_downstream、get_shortest、遍历、连通分量、pagerank、中心性、社区发现、结构统计、
+      最小生成树、单源/全源最短路、网络流、二分图、k 条最短路、着色与团、欧拉路径、拓扑排序与传递闭包、随机游走与采样
+    - astar、vf2_matches、is_isomorphic 要把节点数据交给闭包，Graph 和 FrozenGraph 上各有一个，共用同一份实现
+    - 只在 Graph 上的：读写节点名或数据的（*_names、*_by_name、get_cycle_names）、复制到新 ArenaList 的
+      （induced_subgraph、ego_graph、transitive_reduction_to_arena_list、spanning_forest_to_arena_list）、修改图的（transitive_reduction）
+
+节点名的类型 K 是泛型，可以是 String、整数、UUID 等任意 Hash + Eq 的类型
+- 节点名只在 Node::name 中存放一份，nodes_names 只存哈希值，见 key_index
+- 按名字查询时借助 Borrow，K = String 时可以直接用 &str 查询
+ */
+
+mod key_index;
+mod adjacency;
+mod frozen;
+mod pagerank;
+mod components;
+mod scored;
+mod mst;
+mod astar;
+mod bellman_ford;
+mod dijkstra;
+mod all_pairs;
+mod flow;
+mod bipartite;
+mod yen;
+mod centrality;
+mod community;
+mod transitive;
+mod subgraph;
+mod isomorphism;
+mod coloring;
+mod euler;
+mod traversal;
+mod sampling;
+mod stats;
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use crate::error::{Error, Result};
+use key_index::KeyIndex;
+
+pub use adjacency::Adjacency;
+pub use frozen::FrozenGraph;
+pub use pagerank::PageRankOptions;
+pub use mst::SpanningForest;
+pub use bellman_ford::ShortestPaths;
+pub use all_pairs::DistanceMatrix;
+pub use flow::MaxFlow;
+pub use bipartite::Bipartition;
+pub use community::{Communities, CommunityOptions};
+pub use transitive::Reachability;
+pub use subgraph::Direction;
+pub use isomorphism::MatchMode;
+pub use sampling::WalkOptions;
+pub use traversal::{Bfs, Control, Dfs, VisitEvent};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node<K, T> {
+    name: Option<K>,
+    // 匿名节点为 None，也不进入 nodes_names
+    data: T,
+    next_idx: HashSet<usize>,
+    prev_idx: HashSet<usize>,
+    // 用 HashSet 防止边重复
+}
+
+// 序列化时只保存 nodes，反序列化时重建 nodes_names（其中的哈希值与进程相关，不能保存）
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(
+    try_from = "ArenaListRepr<K, T>",
+    bound(deserialize = "K: Hash + Eq + serde::Deserialize<'de>, T: serde::Deserialize<'de>")
+))]
+pub struct ArenaList<K, T> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    nodes_names: KeyIndex,
+    // 节点名 -> idx，用于快速检索边所在的 index
+    pub nodes: Vec<Node<K, T>>,
+    // 存放数据本身
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ArenaListRepr<K, T> {
+    nodes: Vec<Node<K, T>>,
+}
+
+// 数据可能被篡改或损坏：重名节点、越界的 index、只有一侧的边都返回错误，避免之后 panic
+#[cfg(feature = "serde")]
+impl<K: Hash + Eq, T> TryFrom<ArenaListRepr<K, T>> for ArenaList<K, T> {
+    type Error = Error;
+
+    fn try_from(repr: ArenaListRepr<K, T>) -> Result<Self> {
+        let mut res = Self { nodes_names: KeyIndex::default(), nodes: repr.nodes };
+        let n = res.nodes.len();
+        for idx in 0..n {
+            let node = &res.nodes[idx];
+            if let Some(&bad_idx) = node.next_idx.iter().chain(&node.prev_idx).find(|&&other_idx| other_idx >= n) {
+                return Err(Error::UnknownNode(bad_idx));
+            }
+            if let Some(&dst_idx) = node.next_idx.iter().find(|dst_idx| !res.nodes[**dst_idx].prev_idx.contains(&idx)) {
+                return Err(Error::UnknownEdge(idx, dst_idx));
+            }
+            if let Some(&src_idx) = node.prev_idx.iter().find(|src_idx| !res.nodes[**src_idx].next_idx.contains(&idx)) {
+                return Err(Error::UnknownEdge(src_idx, idx));
+            }
+            if let Some(name) = &node.name {
+                if let Some(&other_idx) = res.nodes_names.get(&res.nodes, name) {
+                    return Err(Error::DuplicateName(other_idx));
+                }
+            }
+            res.nodes_names.insert(&res.nodes, idx);
+        }
+        Ok(res)
+    }
+}
+
+
+impl<K, T> ArenaList<K, T> {
+    pub fn get_node(&self, idx: usize) -> &Node<K, T> {
+        &self.nodes[idx]
+    }
+
+    pub fn try_get_node(&self, idx: usize) -> Result<&Node<K, T>> {
+        self.nodes.get(idx).ok_or(Error::UnknownNode(idx))
+    }
+
+    pub fn get_name_by_idx(&self, idx: usize) -> Option<&K> {
+        self.nodes[idx].name.as_ref()
+    }
+
+    pub fn try_get_name_by_idx(&self, idx: usize) -> Result<Option<&K>> {
+        Ok(self.try_get_node(idx)?.name.as_ref())
+    }
+
+    pub fn add_edge(&mut self, src_idx: usize, dst_idx: usize) {
+        self.nodes[src_idx].next_idx.insert(dst_idx);
+        self.nodes[dst_idx].prev_idx.insert(src_idx);
+    }
+
+    pub fn try_add_edge(&mut self, src_idx: usize, dst_idx: usize) -> Result<()> {
+        self.try_get_node(src_idx)?;
+        self.try_get_node(dst_idx)?;
+        self.add_edge(src_idx, dst_idx);
+        Ok(())
+    }
+
+
+    pub fn del_edge(&mut self, src_idx: usize, dst_idx: usize) -> bool {
+        self.try_del_edge(src_idx, dst_idx).is_ok()
+    }
+
+    pub fn try_del_edge(&mut self, src_idx: usize, dst_idx: usize) -> Result<()> {
+        self.try_get_node(src_idx)?;
+        self.try_get_node(dst_idx)?;
+        if !self.nodes[src_idx].next_idx.remove(&dst_idx) {
+            return Err(Error::UnknownEdge(src_idx, dst_idx));
+        }
+        self.nodes[dst_idx].prev_idx.remove(&src_idx);
+        Ok(())
+    }
+}
+
+
+impl<K: Hash + Eq, T> ArenaList<K, T> {
+    pub fn new() -> Self {
+        Self {
+            nodes_names: KeyIndex::default(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes_names.clear();
+        self.nodes.clear();
+    }
+
+    // 新增一个节点，并返回其索引号
+    pub fn add_node(&mut self, name: K, data: T) -> usize {
+        // 如果已有节点名，则更新节点，如果没有则新建。返回节点号。
+        match self.nodes_names.get(&self.nodes, &name) {
+            Some(&idx) => {
+                self.nodes[idx].data = data;
+                idx
+            }
+            None => {
+                let len = self.nodes.len();
+                self.nodes.push(Node {
+                    name: Some(name),
+                    data,
+                    next_idx: HashSet::new(),
+                    prev_idx: HashSet::new(),
+                });
+                self.nodes_names.insert(&self.nodes, len);
+                len
+            }
+        }
+    }
+
+    // 新增一个匿名节点，并返回其索引号。匿名节点只能通过 index 访问
+    pub fn add_anonymous_node(&mut self, data: T) -> usize {
+        self.nodes.push(Node {
+            name: None,
+            data,
+            next_idx: HashSet::new(),
+            prev_idx: HashSet::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    // 由快照新建 ArenaList，节点的 index 与快照中的顺序一致。快照中有重名节点或越界的边时返回错误
+    pub fn try_from_snapshot(snapshot: GraphSnapshot<K, T>) -> Result<Self> {
+        let mut res = Self::new();
+        for (name, data) in snapshot.nodes {
+            match name {
+                Some(name) => {
+                    if let Some(&idx) = res.get_idx_by_name(&name) {
+                        return Err(Error::DuplicateName(idx));
+                    }
+                    res.add_node(name, data)
+                }
+                None => res.add_anonymous_node(data),
+            };
+        }
+        for (src_idx, dst_idx) in snapshot.edges {
+            res.try_add_edge(src_idx, dst_idx)?;
+        }
+        Ok(res)
+    }
+
+    // 根据 节点名字 返回 index
+    pub fn get_idx_by_name<Q>(&self, name: &Q) -> Option<&usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes_names.get(&self.nodes, name)
+    }
+
+    pub fn try_get_idx_by_name<Q>(&self, name: &Q) -> Result<usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_idx_by_name(name).copied().ok_or(Error::UnknownName)
+    }
+
+    // 节点改名，同时更新 Node::name 和 nodes_names。新名字被其它节点占用时返回错误，不做任何修改
+    // 匿名节点改名后成为具名节点
+    pub fn try_rename_node(&mut self, idx: usize, new_name: K) -> Result<()> {
+        self.try_get_node(idx)?;
+        match self.nodes_names.get(&self.nodes, &new_name) {
+            Some(&other_idx) if other_idx == idx => return Ok(()),
+            Some(&other_idx) => return Err(Error::DuplicateName(other_idx)),
+            None => {}
+        }
+        self.nodes_names.remove(&self.nodes, idx);
+        self.nodes[idx].name = Some(new_name);
+        self.nodes_names.insert(&self.nodes, idx);
+        Ok(())
+    }
+
+    pub fn del_node(&mut self, idx: usize) -> bool {
+        self.try_del_node(idx).is_ok()
+    }
+
+    // 删除节点。移除指向该节点的边，然后移除该节点指向的边，最后移除节点
+    pub fn try_del_node(&mut self, idx: usize) -> Result<()> {
+        self.try_get_node(idx)?;
+
+        let last_idx = self.nodes.len() - 1;
+
+        // step0: 更新 nodes_names。需要在 swap_remove 之前做，因为要读取节点名
+        self.nodes_names.remove(&self.nodes, idx);
+        if idx != last_idx {
+            self.nodes_names.relocate(&self.nodes, last_idx, idx);
+        }
+
+        // 要被删除的节点
+        let node_to_del = self.nodes.swap_remove(idx);
+
+        // step1:上游节点不再指向它。自环随节点一起删除，跳过
+        for src_idx in node_to_del.prev_idx {
+            if src_idx == idx {
+                continue;
+            }
+            if src_idx == last_idx {
+                //如果上游节点恰好是最后一个，要注意它已经移动到新位置了
+                self.nodes[idx].next_idx.remove(&idx);
+            } else {
+                self.nodes[src_idx].next_idx.remove(&idx);
+            }
+        }
+
+        // step2:下游节点也不再指向它
+        for dst_idx in node_to_del.next_idx {
+            if dst_idx == idx {
+                continue;
+            }
+            if dst_idx == last_idx {
+                self.nodes[idx].prev_idx.remove(&idx);
+            } else { self.nodes[dst_idx].prev_idx.remove(&idx); }
+        }
+
+        // 如果要删除的恰好是最后一个，到此结束
+        if idx == last_idx {
+            return Ok(());
+        }
+
+        // step3: 更正其上游向它的指向
+        for src_idx in self.nodes[idx].prev_idx.clone() {
+            if src_idx == last_idx { // 上游节点是自己
+                self.nodes[idx].next_idx.remove(&last_idx);
+                self.nodes[idx].next_idx.insert(idx);
+            } else {
+                self.nodes[src_idx].next_idx.remove(&last_idx);
+                self.nodes[src_idx].next_idx.insert(idx);
+            }
+        }
+        // step4: 更正其下游向它的指向
+        for dst_idx in self.nodes[idx].next_idx.clone() {
+            if dst_idx == last_idx {
+                self.nodes[idx].prev_idx.remove(&last_idx);
+                self.nodes[idx].prev_idx.insert(idx);
+            } else {
+                self.nodes[dst_idx].prev_idx.remove(&last_idx);
+                self.nodes[dst_idx].prev_idx.insert(idx);
+            }
+        }
+        Ok(())
+    }
+}
+
+
+impl<K: Hash + Eq, T> Default for ArenaList<K, T> {
+    fn default() -> Self { Self::new() }
+}
+
+
+// Graph 的快照。Graph 持有 ArenaList 的 &mut 借用，无法直接序列化，通过快照来保存/传输
+// nodes 按 index 顺序存放 (name, data)，匿名节点的 name 为 None。edges 存放 (src_idx, dst_idx)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphSnapshot<K, T> {
+    pub nodes: Vec<(Option<K>, T)>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+
+pub struct Graph<'a, K, T> {
+    owner: &'a mut ArenaList<K, T>,
+}
+
+impl<'a, K, T> Graph<'a, K, T> {
+    pub fn new(arena_list: &'a mut ArenaList<K, T>) -> Self {
+        Self {
+            owner: arena_list
+        }
+    }
+
+    // 增加边
+    pub fn add_edge(&mut self, src_idx: usize, dst_idx: usize) {
+        self.owner.add_edge(src_idx, dst_idx);
+    }
+
+    pub fn try_add_edge(&mut self, src_idx: usize, dst_idx: usize) -> Result<()> {
+        self.owner.try_add_edge(src_idx, dst_idx)
+    }
+
+    // 获取节点
+    pub fn get_node_by_idx(&self, idx: usize) -> &Node<K, T> {
+        self.owner.get_node(idx)
+    }
+
+    pub fn try_get_node_by_idx(&self, idx: usize) -> Result<&Node<K, T>> {
+        self.owner.try_get_node(idx)
+    }
+
+    // 匿名节点返回 None
+    pub fn get_name_by_idx(&self, idx: usize) -> Option<&K> {
+        self.owner.get_name_by_idx(idx)
+    }
+
+    pub fn try_get_name_by_idx(&self, idx: usize) -> Result<Option<&K>> {
+        self.owner.try_get_name_by_idx(idx)
+    }
+
+    // 把一串节点 index（例如路径）转为节点名，匿名节点为 None
+    pub fn get_names_by_idxes(&self, idxes: &[usize]) -> Vec<Option<&K>> {
+        idxes.iter().map(|&idx| self.get_name_by_idx(idx)).collect()
+    }
+
+    // 把错误中携带的环（负环、奇环、有向环）转为节点名，见 Error::cycle
+    pub fn get_cycle_names(&self, err: &Error) -> Option<Vec<Option<&K>>> {
+        err.cycle().map(|cycle| self.get_names_by_idxes(cycle))
+    }
+
+    // 删除边
+    pub fn del_edge_by_idx(&mut self, src_idx: usize, dst_idx: usize) -> bool { self.owner.del_edge(src_idx, dst_idx) }
+    pub fn try_del_edge_by_idx(&mut self, src_idx: usize, dst_idx: usize) -> Result<()> { self.owner.try_del_edge(src_idx, dst_idx) }
+}
+
+impl<'a, K: Hash + Eq, T> Graph<'a, K, T> {
+    // 增加节点
+    pub fn add_node(&mut self, name: K, data: T) -> usize {
+        self.owner.add_node(name, data)
+    }
+
+    // 增加匿名节点
+    pub fn add_anonymous_node(&mut self, data: T) -> usize {
+        self.owner.add_anonymous_node(data)
+    }
+
+    // 按节点名增加边，节点名不存在时返回错误
+    pub fn try_add_edge_by_name<Q>(&mut self, src_name: &Q, dst_name: &Q) -> Result<()>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let src_idx = self.try_get_idx_by_name(src_name)?;
+        let dst_idx = self.try_get_idx_by_name(dst_name)?;
+        self.try_add_edge(src_idx, dst_idx)
+    }
+
+    // 增加边
+    pub fn add_node_and_edge(&mut self, src_name: K, src_data: T, dst_name: K, dst_data: T) {
+        let src_idx = self.add_node(src_name, src_data);
+        let dst_idx = self.add_node(dst_name, dst_data);
+        self.add_edge(src_idx, dst_idx);
+    }
+
+
+    pub fn get_idx_by_name<Q>(&self, name: &Q) -> Option<&usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.owner.get_idx_by_name(name)
+    }
+
+    pub fn try_get_idx_by_name<Q>(&self, name: &Q) -> Result<usize>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.owner.try_get_idx_by_name(name)
+    }
+
+    // 把按 index 存放的结果（例如各节点的得分）转为 {节点名: 值}，匿名节点不出现在结果中
+    pub fn to_name_map<V: Clone>(&self, values: &[V]) -> HashMap<&K, V> {
+        self.owner.nodes.iter().zip(values)
+            .filter_map(|(node, value)| node.name.as_ref().map(|name| (name, value.clone())))
+            .collect()
+    }
+
+
+    // 删除节点
+    pub fn del_node_by_idx(&mut self, idx: usize) -> bool { self.owner.del_node(idx) }
+    pub fn try_del_node_by_idx(&mut self, idx: usize) -> Result<()> { self.owner.try_del_node(idx) }
+    // 删除节点
+    pub fn del_node_by_name<Q>(&mut self, name: &Q) -> bool
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.try_del_node_by_name(name).is_ok()
+    }
+
+    pub fn try_del_node_by_name<Q>(&mut self, name: &Q) -> Result<()>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let idx = self.try_get_idx_by_name(name)?;
+        self.try_del_node_by_idx(idx)
+    }
+
+    // 删除边
+    pub fn del_edge_by_name<Q>(&mut self, src_name: &Q, dst_name: &Q) -> bool
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.try_del_edge_by_name(src_name, dst_name).is_ok()
+    }
+
+    pub fn try_del_edge_by_name<Q>(&mut self, src_name: &Q, dst_name: &Q) -> Result<()>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let src_idx = self.try_get_idx_by_name(src_name)?;
+        let dst_idx = self.try_get_idx_by_name(dst_name)?;
+        self.try_del_edge_by_idx(src_idx, dst_idx)
+    }
+
+    // 节点改名
+    pub fn rename_node_by_idx(&mut self, idx: usize, new_name: K) -> bool {
+        self.try_rename_node_by_idx(idx, new_name).is_ok()
+    }
+
+    pub fn try_rename_node_by_idx(&mut self, idx: usize, new_name: K) -> Result<()> {
+        self.owner.try_rename_node(idx, new_name)
+    }
+
+    pub fn rename_node_by_name<Q>(&mut self, name: &Q, new_name: K) -> bool
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.try_rename_node_by_name(name, new_name).is_ok()
+    }
+
+    pub fn try_rename_node_by_name<Q>(&mut self, name: &Q, new_name: K) -> Result<()>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let idx = self.try_get_idx_by_name(name)?;
+        self.try_rename_node_by_idx(idx, new_name)
+    }
+
+    pub fn clear(&mut self) { self.owner.clear() }
+
+    // 保存为快照。快照持有数据本身，不借用 ArenaList，可以跨线程、跨进程传递
+    pub fn save(&self) -> GraphSnapshot<K, T> where K: Clone, T: Clone {
+        GraphSnapshot {
+            nodes: self.owner.nodes.iter().map(|node| (node.name.clone(), node.data.clone())).collect(),
+            edges: self.get_all_edges(),
+        }
+    }
+
+    // 从快照加载。会先清空已有数据，节点的 index 与快照中的顺序一致
+    // 快照中有重名节点或越界的边时 panic，见 try_load
+    pub fn load(&mut self, snapshot: GraphSnapshot<K, T>) {
+        self.try_load(snapshot).expect("invalid snapshot");
+    }
+
+    // 从快照加载，快照中有重名节点或越界的边时返回错误。出错时图保持原样
+    pub fn try_load(&mut self, snapshot: GraphSnapshot<K, T>) -> Result<()> {
+        *self.owner = ArenaList::try_from_snapshot(snapshot)?;
+        Ok(())
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, T: Clone> Graph<'a, K, T> {
+    // 复制所有节点（名字、数据）到一个新的 ArenaList，只保留给定的边。节点 index 不变
+    pub fn clone_with_edges(&self, edges: impl IntoIterator<Item = (usize, usize)>) -> ArenaList<K, T> {
+        let mut arena_list = ArenaList::new();
+        for node in &self.owner.nodes {
+            match &node.name {
+                Some(name) => arena_list.add_node(name.clone(), node.data.clone()),
+                None => arena_list.add_anonymous_node(node.data.clone()),
+            };
+        }
+        for (src_idx, dst_idx) in edges {
+            arena_list.add_edge(src_idx, dst_idx);
+        }
+        arena_list
+    }
+}
+
+impl<'a, K: Hash + Eq + Debug, T> Graph<'a, K, T> {
+    // 打印所有节点的 name
+    pub fn print_nodes(&self) {
+        println!("{:?}", self.owner.nodes.iter().map(|x| x.name.as_ref()).collect::<Vec<Option<&K>>>());
+    }
+    // 打印所有的边
+    pub fn print_edges(&self) {
+        // 获取所有的边(返回值是节点的 index)
+        let edges = self.get_all_edges();
+        for (src_idx, dst_idx) in edges {
+            println!("{:?}->{:?}", self.get_name_by_idx(src_idx), self.get_name_by_idx(dst_idx));
+        }
+    }
+}
+
+impl<'a, K, T> Adjacency for Graph<'a, K, T> {
+    fn node_count(&self) -> usize { self.owner.nodes.len() }
+
+    fn successors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.owner.nodes[idx].next_idx.iter().copied()
+    }
+
+    fn predecessors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.owner.nodes[idx].prev_idx.iter().copied()
+    }
+
+    fn has_edge(&self, src_idx: usize, dst_idx: usize) -> bool {
+        self.owner.nodes[src_idx].next_idx.contains(&dst_idx)
+    }
+}
+
+impl<'a, K, T> Graph<'a, K, T> {
+    // 同 get_downstream，但先检查 batch_idx，有不存在的节点时返回 Error::UnknownNode
+    pub fn try_get_downstream(&self, batch_idx: Vec<usize>, max_level: usize) -> Result<HashMap<usize, Vec<usize>>> {
+        for &idx in &batch_idx {
+            self.try_get_node_by_idx(idx)?;
+        }
+        Ok(self.get_downstream(batch_idx, max_level))
+    }
+
+    pub fn try_get_shortest(&self, src_idx: usize, dst_idx: usize, max_level: usize) -> Result<Option<usize>> {
+        self.try_get_node_by_idx(src_idx)?;
+        self.try_get_node_by_idx(dst_idx)?;
+        Ok(self.get_shortest(src_idx, dst_idx, max_level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{Hash, Hasher};
+    use crate::graph::{Adjacency, ArenaList, Graph, GraphSnapshot};
+    use crate::Error;
+
+
+
+    #[test]
+    fn test1() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("John", "Emma")
+            , ("Sophia", "Tom")
+            , ("Isabella", "Emma")
+            , ("Tom", "Isabella")
+            , ("Tom", "John")
+            , ("Tom", "Michael")
+            , ("John", "Emma")
+            , ("Tom", "Sophia")
+            , ("Oliver", "Emma")
+            , ("Michael", "Daniel")
+            , ("Michael", "Lucy")
+            , ("Sophia", "Michael")
+            , ("Oliver", "Lucy")
+            , ("Sophia", "Emily")
+            , ("Michael", "Daniel")
+            , ("Sophia", "Michael")
+            , ("Michael", "Sophia")
+            , ("John", "Emma")
+            , ("Tom", "Sophia")
+            , ("Sophia", "John")]
+            ;
+
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(
+                src_name, src_name.to_string(),
+                dst_name, dst_name.to_string());
+        }
+
+        // 获取所有的边(返回值是节点的 index)
+        let edges = graph.get_all_edges();
+        assert_eq!(edges.len(), 15);
+
+        // 打印所有的边
+        graph.print_edges();
+
+        // 打印所有节点
+        graph.print_nodes();
+
+        graph.del_edge_by_name("Michael", "Lucy");
+        println!("======after del edge [Sophia]-> [Lucy]：======");
+        graph.print_edges();
+
+        graph.del_node_by_name("Sophia");
+        println!("======after del node 【Sophia】：======");
+        graph.print_edges();
+        graph.clear();
+    }
+
+
+    #[test]
+    fn test2() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("John", "Emma")
+            , ("Sophia", "Tom")
+            , ("Isabella", "Emma")
+            , ("Tom", "Isabella")
+            , ("Tom", "John")
+            , ("Tom", "Michael")
+            , ("John", "Emma")
+            , ("Tom", "Sophia")
+            , ("Oliver", "Emma")
+            , ("Michael", "Daniel")
+            , ("Michael", "Lucy")
+            , ("Sophia", "Michael")
+            , ("Oliver", "Lucy")
+            , ("Sophia", "Emily")
+            , ("Michael", "Daniel")
+            , ("Sophia", "Michael")
+            , ("Michael", "Sophia")
+            , ("John", "Emma")
+            , ("Tom", "Sophia")
+            , ("Sophia", "John")]
+            ;
+
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(
+                src_name, src_name.to_string(),
+                dst_name, dst_name.to_string());
+        }
+
+        let idxes = vec![2];
+        let level_order = graph.get_downstream(idxes, 100000000);
+        for level in 0..level_order.len() {
+            println!("[level = {}], idx = {:?}", level, level_order.get(&level));
+        }
+
+        println!("=====print names=====");
+        for level in 0..level_order.len() {
+            let node_names: Vec<Option<&&str>> =
+                level_order.get(&level).unwrap()
+                    .iter().map(|idx| graph.get_name_by_idx(*idx))
+                    .collect();
+            println!("[level = {}], names = {:?}", level, node_names);
+        }
+
+        println!("2 到 7 的最短路径长度为： {}",graph.get_shortest(2,7,1000000).unwrap());
+    }
+
+
+    #[test]
+    fn test_save_load() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("John", 1, "Emma", 2);
+        graph.add_node_and_edge("Emma", 2, "Tom", 3);
+        graph.add_node_and_edge("Tom", 3, "John", 1);
+        let snapshot = graph.save();
+
+        let mut arena_list2 = ArenaList::new();
+        let mut graph2 = Graph::new(&mut arena_list2);
+        graph2.load(snapshot);
+        assert_eq!(graph2.get_idx_by_name("Tom"), Some(&2));
+        assert_eq!(graph2.get_shortest(0, 2, 10), Some(2));
+        assert_eq!(graph2.get_node_by_idx(1).data, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid snapshot")]
+    fn test_load_duplicate_name() {
+        // 重名节点不能合并，否则之后的 index 全部错位
+        let snapshot = GraphSnapshot { nodes: vec![(Some("John"), 1), (Some("John"), 2), (Some("Emma"), 3)], edges: vec![(0, 2)] };
+        let mut arena_list = ArenaList::new();
+        Graph::new(&mut arena_list).load(snapshot);
+    }
+
+    #[test]
+    fn test_try() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("John", 1, "Emma", 2);
+        graph.add_node_and_edge("Tom", 3, "Tom", 3);
+
+        assert_eq!(graph.try_get_node_by_idx(5).err(), Some(Error::UnknownNode(5)));
+        assert_eq!(graph.try_add_edge(0, 9), Err(Error::UnknownNode(9)));
+        assert_eq!(graph.try_get_idx_by_name("Lucy"), Err(Error::UnknownName));
+        assert_eq!(graph.try_del_edge_by_idx(1, 0), Err(Error::UnknownEdge(1, 0)));
+        assert_eq!(graph.try_get_shortest(0, 7, 10), Err(Error::UnknownNode(7)));
+        assert_eq!(graph.try_del_edge_by_name("John", "Emma"), Ok(()));
+        assert!(graph.get_node_by_idx(1).prev_idx.is_empty());
+
+        // 删除带自环的最后一个节点
+        assert_eq!(graph.try_del_node_by_name("Tom"), Ok(()));
+        assert_eq!(graph.try_del_node_by_name("Tom"), Err(Error::UnknownName));
+        assert!(!graph.del_node_by_idx(2));
+
+        // 加载失败时图保持原样
+        let snapshot = GraphSnapshot { nodes: vec![(Some("Lucy"), 5), (None, 6)], edges: vec![(0, 1), (1, 2)] };
+        assert_eq!(graph.try_load(snapshot), Err(Error::UnknownNode(2)));
+        assert_eq!(graph.get_idx_by_name("Emma"), Some(&1));
+        assert_eq!(graph.get_idx_by_name("Lucy"), None);
+        let snapshot = GraphSnapshot { nodes: vec![(Some("Lucy"), 5), (Some("Lucy"), 6)], edges: vec![] };
+        assert_eq!(graph.try_load(snapshot), Err(Error::DuplicateName(0)));
+        assert_eq!(graph.get_node_by_idx(0).data, 1);
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("John", 1, "Emma", 2);
+        let anonymous_idx = graph.add_anonymous_node(3);
+        graph.add_edge(anonymous_idx, 0);
+        assert_eq!(graph.get_name_by_idx(anonymous_idx), None);
+
+        assert_eq!(graph.try_rename_node_by_name("John", "Emma"), Err(Error::DuplicateName(1)));
+        assert_eq!(graph.try_rename_node_by_name("John", "Tom"), Ok(()));
+        assert_eq!(graph.get_idx_by_name("John"), None);
+        assert_eq!(graph.get_idx_by_name("Tom"), Some(&0));
+        assert_eq!(graph.get_shortest(anonymous_idx, 1, 10), Some(2));
+
+        // 匿名节点改名后可以按名字访问
+        assert!(graph.rename_node_by_idx(anonymous_idx, "Lucy"));
+        assert_eq!(graph.get_idx_by_name("Lucy"), Some(&anonymous_idx));
+
+        // 删除节点后，被移动的匿名节点依然可以正常使用
+        let anonymous_idx = graph.add_anonymous_node(4);
+        graph.add_edge(anonymous_idx, 1);
+        assert!(graph.del_node_by_name("Tom"));
+        assert_eq!(graph.get_name_by_idx(0), None);
+        assert_eq!(graph.get_shortest(0, 1, 10), Some(1));
+    }
+
+    #[test]
+    fn test_generic_name() {
+        // K = String 时，可以直接用 &str 查询
+        let mut arena_list: ArenaList<String, i32> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("John".to_string(), 1, "Emma".to_string(), 2);
+        assert_eq!(graph.get_idx_by_name("Emma"), Some(&1));
+        assert!(graph.del_edge_by_name("John", "Emma"));
+
+        // 整数作为节点名
+        let mut arena_list: ArenaList<u64, ()> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        for i in 0..10u64 {
+            graph.add_node_and_edge(i, (), i + 1, ());
+        }
+        assert_eq!(graph.get_shortest(*graph.get_idx_by_name(&3).unwrap(), *graph.get_idx_by_name(&8).unwrap(), 100), Some(5));
+        assert!(graph.del_node_by_name(&0));
+        assert_eq!(graph.get_name_by_idx(0), Some(&10));
+        assert_eq!(graph.get_idx_by_name(&10), Some(&0));
+    }
+
+    #[test]
+    fn test_hash_collision() {
+        // 所有节点名的哈希值都相同，检验 nodes_names 对哈希冲突的处理
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct SameHash(u32);
+        impl Hash for SameHash {
+            fn hash<H: Hasher>(&self, state: &mut H) { 0u8.hash(state) }
+        }
+
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        for i in 0..5 {
+            assert_eq!(graph.add_node(SameHash(i), i), i as usize);
+        }
+        assert_eq!(graph.get_idx_by_name(&SameHash(3)), Some(&3));
+        assert!(graph.del_node_by_name(&SameHash(0)));
+        assert!(graph.del_node_by_name(&SameHash(2)));
+        assert_eq!(graph.get_idx_by_name(&SameHash(0)), None);
+        assert_eq!(graph.get_idx_by_name(&SameHash(4)), Some(&0));
+        assert_eq!(graph.get_idx_by_name(&SameHash(3)), Some(&2));
+        assert_eq!(graph.try_rename_node_by_name(&SameHash(1), SameHash(3)), Err(Error::DuplicateName(2)));
+        assert!(graph.rename_node_by_name(&SameHash(1), SameHash(7)));
+        assert_eq!(graph.get_idx_by_name(&SameHash(7)), Some(&1));
+        assert_eq!(graph.add_node(SameHash(3), 30), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        graph.add_node_and_edge("John", 1, "Emma", 2);
+        graph.add_node_and_edge("Emma", 2, "Tom", 3);
+
+        let json = serde_json::to_string(&graph.save()).unwrap();
+        let mut arena_list2: ArenaList<String, i32> = ArenaList::new();
+        let mut graph2 = Graph::new(&mut arena_list2);
+        graph2.load(serde_json::from_str(&json).unwrap());
+        assert_eq!(graph2.get_all_edges().len(), 2);
+
+        // ArenaList 本身也可以直接序列化
+        let json = serde_json::to_string(&arena_list).unwrap();
+        let arena_list3: ArenaList<String, i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(arena_list3.get_idx_by_name("Tom"), Some(&2));
+
+        // 重名、越界、只有一侧的边：反序列化失败，而不是得到一个之后会 panic 的图
+        let node = |name: &str, next: &str, prev: &str| format!(r#"{{"name":"{name}","data":0,"next_idx":[{next}],"prev_idx":[{prev}]}}"#);
+        let bad_jsons = [
+            format!(r#"{{"nodes":[{},{}]}}"#, node("A", "", ""), node("A", "", "")),
+            format!(r#"{{"nodes":[{},{}]}}"#, node("A", "5", ""), node("B", "", "")),
+            format!(r#"{{"nodes":[{},{}]}}"#, node("A", "1", ""), node("B", "", "")),
+        ];
+        for json in bad_jsons {
+            assert!(serde_json::from_str::<ArenaList<String, i32>>(&json).is_err());
+        }
+        let json = format!(r#"{{"nodes":[{},{}]}}"#, node("A", "1", ""), node("B", "", "0"));
+        assert!(serde_json::from_str::<ArenaList<String, i32>>(&json).is_ok());
+    }
+}
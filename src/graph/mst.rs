@@ -0,0 +1,158 @@
+/*
+最小生成树/森林
+- 把有向边看作无向边，边权由调用方给出：weight(src_idx, dst_idx)
+- A->B 与 B->A 同时存在时，两者都是候选边，自然取较小的那个；自环忽略
+- 图不连通时，返回的是最小生成森林，每个连通分量一棵树
+- Kruskal：所有边排序后用并查集挑选，O(m log m)
+- Prim：从每个还没访问过的节点出发，用堆扩展，O(m log m)
+*/
+
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use crate::union_find::UnionFind;
+use super::scored::MinScored;
+use super::adjacency::Adjacency;
+use super::{ArenaList, Graph};
+
+
+pub struct SpanningForest {
+    // 选中的边 (src_idx, dst_idx, weight)，方向与原图中的边一致
+    pub edges: Vec<(usize, usize, f64)>,
+    pub total_weight: f64,
+}
+
+impl SpanningForest {
+    fn from_edges(edges: Vec<(usize, usize, f64)>) -> Self {
+        let total_weight = edges.iter().map(|edge| edge.2).sum();
+        Self { edges, total_weight }
+    }
+}
+
+
+// Adjacency::mst_kruskal 的实现
+pub(super) fn mst_kruskal<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64) -> SpanningForest {
+    let mut edges: Vec<(usize, usize, f64)> = graph.get_all_edges().into_iter()
+        .filter(|(src_idx, dst_idx)| src_idx != dst_idx)
+        .map(|(src_idx, dst_idx)| (src_idx, dst_idx, weight(src_idx, dst_idx)))
+        .collect();
+    edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let mut uf = UnionFind::new(graph.node_count());
+    let chosen = edges.into_iter()
+        .filter(|&(src_idx, dst_idx, _)| uf.union(src_idx, dst_idx))
+        .collect();
+    SpanningForest::from_edges(chosen)
+}
+
+// Adjacency::mst_prim 的实现
+pub(super) fn mst_prim<G: Adjacency + ?Sized>(graph: &G, weight: impl Fn(usize, usize) -> f64) -> SpanningForest {
+    let n = graph.node_count();
+    let mut visited = vec![false; n];
+    let mut chosen = Vec::new();
+    // 堆中存放 (weight, (src_idx, dst_idx, 新加入的节点))
+    let mut heap = BinaryHeap::new();
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        let mut curr = root;
+        loop {
+            visited[curr] = true;
+            for dst_idx in graph.successors(curr) {
+                if !visited[dst_idx] {
+                    heap.push(MinScored(weight(curr, dst_idx), (curr, dst_idx, dst_idx)));
+                }
+            }
+            for src_idx in graph.predecessors(curr) {
+                if !visited[src_idx] {
+                    heap.push(MinScored(weight(src_idx, curr), (src_idx, curr, src_idx)));
+                }
+            }
+            // 弹出连向树外的最小边
+            let next = loop {
+                match heap.pop() {
+                    Some(MinScored(w, (src_idx, dst_idx, new_idx))) if !visited[new_idx] => {
+                        chosen.push((src_idx, dst_idx, w));
+                        break Some(new_idx);
+                    }
+                    Some(_) => continue,
+                    None => break None,
+                }
+            };
+            match next {
+                Some(new_idx) => curr = new_idx,
+                None => break,
+            }
+        }
+    }
+    SpanningForest::from_edges(chosen)
+}
+
+
+impl<'a, K: Hash + Eq + Clone, T: Clone> Graph<'a, K, T> {
+    // 把生成森林转为一个新的图，存放在新的 ArenaList 中。保留所有节点（名字、数据），节点 index 不变
+    pub fn spanning_forest_to_arena_list(&self, forest: &SpanningForest) -> ArenaList<K, T> {
+        self.clone_with_edges(forest.edges.iter().map(|&(src_idx, dst_idx, _)| (src_idx, dst_idx)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_mst() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("A", "B", 7.0), ("A", "D", 5.0), ("B", "C", 8.0), ("B", "D", 9.0), ("B", "E", 7.0),
+            ("C", "E", 5.0), ("D", "E", 15.0), ("D", "F", 6.0), ("E", "F", 8.0), ("E", "G", 9.0),
+            ("F", "G", 11.0), ("G", "G", 0.0), ("X", "Y", 1.0), ("Y", "X", 3.0)];
+        let mut weights = HashMap::new();
+        for (src_name, dst_name, w) in vec1 {
+            graph.add_node_and_edge(src_name, src_name.to_lowercase(), dst_name, dst_name.to_lowercase());
+            let src_idx = *graph.get_idx_by_name(src_name).unwrap();
+            let dst_idx = *graph.get_idx_by_name(dst_name).unwrap();
+            weights.insert((src_idx, dst_idx), w);
+        }
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+
+        let kruskal = graph.mst_kruskal(weight);
+        let prim = graph.mst_prim(weight);
+        // 主分量 39，X-Y 分量 1
+        assert_eq!(kruskal.total_weight, 40.0);
+        assert_eq!(prim.total_weight, 40.0);
+        assert_eq!(kruskal.edges.len(), 7);
+        assert_eq!(prim.edges.len(), 7);
+
+        let mut forest_arena_list = graph.spanning_forest_to_arena_list(&prim);
+        let forest = Graph::new(&mut forest_arena_list);
+        assert_eq!(forest.get_all_edges().len(), 7);
+        assert_eq!(forest.get_name_by_idx(6), Some(&"G"));
+        assert_eq!(forest.get_node_by_idx(6).data, "g");
+        assert_eq!(forest.weakly_connected_components().len(), 2);
+    }
+
+    #[test]
+    fn test_mst_edge_cases() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 空图、只有自环的图：没有树边
+        for forest in [graph.mst_kruskal(|_, _| 1.0), graph.mst_prim(|_, _| 1.0)] {
+            assert!(forest.edges.is_empty());
+            assert_eq!(forest.total_weight, 0.0);
+        }
+        graph.add_node_and_edge("A", (), "A", ());
+        assert!(graph.mst_prim(|_, _| 1.0).edges.is_empty());
+
+        // 三个孤立节点加一条边：森林只有一条边，负权也照常选取
+        graph.add_node("B", ());
+        graph.add_node_and_edge("C", (), "D", ());
+        for forest in [graph.mst_kruskal(|_, _| -2.0), graph.mst_prim(|_, _| -2.0)] {
+            assert_eq!(forest.edges.len(), 1);
+            assert_eq!(forest.total_weight, -2.0);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+/*
+PageRank，沿 next_idx 传播得分
+- 每轮：score[i] = (1 - damping) * p[i] + damping * (Σ score[j] / out_degree(j) + dangling * p[i])，j 为 i 的上游
+- 悬挂节点（next_idx 为空）的得分不会丢失，按 p 重新分配给所有节点
+- p 是跳转分布。普通 PageRank 为均匀分布，个性化 PageRank 均匀分布在种子节点上
+- 两轮得分差的 L1 范数小于 tolerance，或达到 max_iter 时停止
+- damping 取值 [0, 1]，tolerance 是非负的有限数，max_iter 至少为 1，不合法时返回 Error::InvalidParameter，存放参数名
+只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+返回 Result<Vec<f64>>，按节点 index 存放得分，得分之和为 1。按名字取得分可以用 Graph::to_name_map
+*/
+
+use crate::error::{Error, Result};
+use super::adjacency::{self, Adjacency};
+
+
+pub struct PageRankOptions {
+    // 阻尼系数，沿边走的概率
+    pub damping: f64,
+    // 收敛阈值
+    pub tolerance: f64,
+    // 最大迭代次数
+    pub max_iter: usize,
+}
+
+impl PageRankOptions {
+    fn check(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.damping) {
+            return Err(Error::InvalidParameter("damping"));
+        }
+        if !(self.tolerance.is_finite() && self.tolerance >= 0.0) {
+            return Err(Error::InvalidParameter("tolerance"));
+        }
+        if self.max_iter == 0 {
+            return Err(Error::InvalidParameter("max_iter"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for PageRankOptions {
+    fn default() -> Self {
+        Self { damping: 0.85, tolerance: 1e-6, max_iter: 100 }
+    }
+}
+
+
+// Adjacency::pagerank、personalized_pagerank 的实现，Graph 和 FrozenGraph 都可以使用
+pub(super) fn pagerank<G: Adjacency + ?Sized>(graph: &G, options: &PageRankOptions) -> Result<Vec<f64>> {
+    options.check()?;
+    let n = graph.node_count();
+    Ok(pagerank_with(graph, vec![1.0 / n as f64; n], options))
+}
+
+// 个性化 PageRank，跳转只落在 seeds 上。seeds 为空时等同于普通 PageRank
+pub(super) fn personalized_pagerank<G: Adjacency + ?Sized>(graph: &G, seeds: &[usize], options: &PageRankOptions) -> Result<Vec<f64>> {
+    if seeds.is_empty() {
+        return pagerank(graph, options);
+    }
+    options.check()?;
+    let mut jump = vec![0.0; graph.node_count()];
+    for &idx in seeds {
+        adjacency::check_idx(graph, idx)?;
+        jump[idx] = 1.0;
+    }
+    let total: f64 = jump.iter().sum();
+    jump.iter_mut().for_each(|p| *p /= total);
+    Ok(pagerank_with(graph, jump, options))
+}
+
+fn pagerank_with<G: Adjacency + ?Sized>(graph: &G, jump: Vec<f64>, options: &PageRankOptions) -> Vec<f64> {
+    let n = graph.node_count();
+    let out_degree: Vec<usize> = (0..n).map(|idx| graph.successors(idx).count()).collect();
+    let mut score = jump.clone();
+    for _ in 0..options.max_iter {
+        let dangling: f64 = out_degree.iter().zip(&score)
+            .filter(|(&d, _)| d == 0)
+            .map(|(_, s)| s).sum();
+        let new_score: Vec<f64> = (0..n).map(|idx| {
+            let from_prev: f64 = graph.predecessors(idx)
+                .map(|src_idx| score[src_idx] / out_degree[src_idx] as f64)
+                .sum();
+            (1.0 - options.damping) * jump[idx] + options.damping * (from_prev + dangling * jump[idx])
+        }).collect();
+        let err: f64 = new_score.iter().zip(&score).map(|(a, b)| (a - b).abs()).sum();
+        score = new_score;
+        if err < options.tolerance {
+            break;
+        }
+    }
+    score
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Graph};
+    use crate::graph::pagerank::PageRankOptions;
+    use crate::Error;
+
+    #[test]
+    fn test_pagerank() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // C 被所有人指向；D 是悬挂节点
+        let vec1 = vec![("A", "C"), ("B", "C"), ("C", "A"), ("D", "D"), ("A", "B")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        graph.del_edge_by_name("D", "D");
+
+        let options = PageRankOptions { tolerance: 1e-10, ..Default::default() };
+        let scores = graph.pagerank(&options).unwrap();
+        assert!((scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        let scores = graph.to_name_map(&scores);
+        assert!(scores[&"C"] > scores[&"A"]);
+        assert!(scores[&"A"] > scores[&"B"]);
+        assert!(scores[&"B"] > scores[&"D"]);
+
+        // 个性化：从 D 出发的跳转，D 的得分最高
+        let d_idx = *graph.get_idx_by_name("D").unwrap();
+        let scores = graph.personalized_pagerank(&[d_idx], &options).unwrap();
+        assert!((scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(scores.iter().all(|&s| s <= scores[d_idx]));
+        assert_eq!(graph.personalized_pagerank(&[9], &options), Err(Error::UnknownNode(9)));
+
+        // 冻结后结果相同
+        let expected = graph.pagerank(&options).unwrap();
+        let frozen = arena_list.freeze();
+        let scores = frozen.pagerank(&options).unwrap();
+        assert!(scores.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert_eq!(frozen.personalized_pagerank(&[9], &options), Err(Error::UnknownNode(9)));
+    }
+
+    #[test]
+    fn test_pagerank_edge_cases() {
+        let mut arena_list: ArenaList<&str, ()> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let options = PageRankOptions::default();
+        // 空图
+        assert!(graph.pagerank(&options).unwrap().is_empty());
+        assert_eq!(graph.personalized_pagerank(&[0], &options), Err(Error::UnknownNode(0)));
+
+        // 两个互不相连的二元环，外加一个只有自环的节点：对称，得分都相同
+        for (src_name, dst_name) in [("A", "B"), ("B", "A"), ("C", "D"), ("D", "C"), ("E", "E")] {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let scores = graph.pagerank(&PageRankOptions { tolerance: 1e-12, ..Default::default() }).unwrap();
+        assert!(scores.iter().all(|&s| (s - 0.2).abs() < 1e-9));
+        // seeds 为空时等同于普通 PageRank；跳转只落在 A 上时，另一个分量得分为 0
+        assert_eq!(graph.personalized_pagerank(&[], &options), graph.pagerank(&options));
+        let scores = graph.personalized_pagerank(&[0], &options).unwrap();
+        assert!(scores[2..].iter().all(|&s| s == 0.0));
+        // 只迭代一轮：对称的图上仍然是初始值
+        assert_eq!(graph.pagerank(&PageRankOptions { max_iter: 1, ..Default::default() }).unwrap(), vec![0.2; 5]);
+        // 不合法的参数
+        let bad_options = [
+            (PageRankOptions { damping: 1.5, ..Default::default() }, "damping"),
+            (PageRankOptions { damping: f64::NAN, ..Default::default() }, "damping"),
+            (PageRankOptions { tolerance: -1.0, ..Default::default() }, "tolerance"),
+            (PageRankOptions { tolerance: f64::NAN, ..Default::default() }, "tolerance"),
+            (PageRankOptions { max_iter: 0, ..Default::default() }, "max_iter"),
+        ];
+        for (bad, name) in &bad_options {
+            assert_eq!(graph.pagerank(bad), Err(Error::InvalidParameter(name)));
+            assert_eq!(graph.personalized_pagerank(&[0], bad), Err(Error::InvalidParameter(name)));
+        }
+    }
+}
@@ -0,0 +1,244 @@
+/*
+随机游走与采样，沿 next_idx 走，随机性都来自 seed，相同的 seed 得到相同的结果
+- random_walks(starts, weight, opts)：每个起点走 opts.walks_per_node 次，每次最多 opts.walk_length 个节点（含起点）
+  - 转移概率正比于 weight(src_idx, dst_idx) × node2vec 的偏置，不带权时传入 |_, _| 1.0，权重不是正数的边不走
+  - node2vec 偏置：上一步为 t、当前为 v、候选为 x，x == t 时乘 1/p，t -> x 有边时乘 1，否则乘 1/q
+    p = q = 1 时就是普通的（带权）随机游走；p、q 必须是有限的正数
+  - 每一步以 restart_prob（取值 [0, 1]）的概率回到起点（带重启的随机游走），已经在起点时不重启，回到起点后不再有“上一步”
+  - 走到没有出边的节点时提前结束
+  - p、q、restart_prob 不合法时返回 Error::InvalidParameter，存放参数名
+- random_walk_names：同上，以节点名返回，匿名节点为 None，适合直接喂给 word2vec 一类的模型
+- sample_nodes(k, seed)：均匀地不放回采 k 个节点
+- sample_edges(k, seed)：均匀地不放回采 k 条边
+- snowball_sample(seeds, fanout, max_nodes, seed)：雪球采样，从 seeds 出发一轮一轮扩展，
+  每个节点随机选最多 fanout 个还没采到的下游，直到采满 max_nodes 个节点或者无法扩展
+  结果可以交给 induced_subgraph 得到子图
+random_walk_names 以外都只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用
+*/
+
+use std::collections::HashSet;
+use crate::error::{Error, Result};
+use crate::rng::Rng;
+use super::adjacency::{self, Adjacency};
+use super::Graph;
+
+
+pub struct WalkOptions {
+    pub seed: u64,
+    // 每次游走的节点数上限（含起点）
+    pub walk_length: usize,
+    pub walks_per_node: usize,
+    pub restart_prob: f64,
+    // node2vec 的返回参数 p 和出入参数 q
+    pub p: f64,
+    pub q: f64,
+}
+
+impl WalkOptions {
+    fn check(&self) -> Result<()> {
+        if !(self.p.is_finite() && self.p > 0.0) {
+            return Err(Error::InvalidParameter("p"));
+        }
+        if !(self.q.is_finite() && self.q > 0.0) {
+            return Err(Error::InvalidParameter("q"));
+        }
+        if !(0.0..=1.0).contains(&self.restart_prob) {
+            return Err(Error::InvalidParameter("restart_prob"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self { seed: 0, walk_length: 80, walks_per_node: 10, restart_prob: 0.0, p: 1.0, q: 1.0 }
+    }
+}
+
+
+// 每个节点排好序的下游，每次调用只算一次。HashSet 的遍历顺序每次运行都可能不同，排序后结果才可复现
+fn sorted_adjacency<G: Adjacency + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    (0..graph.node_count()).map(|idx| {
+        let mut next_idxes: Vec<usize> = graph.successors(idx).collect();
+        next_idxes.sort_unstable();
+        next_idxes
+    }).collect()
+}
+
+// Adjacency::random_walks 等的实现
+pub(super) fn random_walks<G: Adjacency + ?Sized>(graph: &G, starts: &[usize], weight: impl Fn(usize, usize) -> f64, opts: &WalkOptions) -> Result<Vec<Vec<usize>>> {
+    opts.check()?;
+    for &idx in starts {
+        adjacency::check_idx(graph, idx)?;
+    }
+    let adjacency = sorted_adjacency(graph);
+    let mut rng = Rng::new(opts.seed);
+    let mut walks = Vec::with_capacity(starts.len() * opts.walks_per_node);
+    let mut weights = Vec::new();
+    for _ in 0..opts.walks_per_node {
+        for &start in starts {
+            if opts.walk_length == 0 {
+                walks.push(Vec::new());
+                continue;
+            }
+            let mut walk = vec![start];
+            let mut prev: Option<usize> = None;
+            while walk.len() < opts.walk_length {
+                let idx = *walk.last().unwrap();
+                if idx != start && opts.restart_prob > 0.0 && rng.next_f64() < opts.restart_prob {
+                    walk.push(start);
+                    prev = None;
+                    continue;
+                }
+                let next_idxes = &adjacency[idx];
+                weights.clear();
+                weights.extend(next_idxes.iter().map(|&next_idx| {
+                    let bias = match prev {
+                        Some(t) if next_idx == t => 1.0 / opts.p,
+                        Some(t) if graph.has_edge(t, next_idx) => 1.0,
+                        Some(_) => 1.0 / opts.q,
+                        None => 1.0,
+                    };
+                    weight(idx, next_idx) * bias
+                }));
+                let Some(i) = rng.gen_weighted(&weights) else { break };
+                walk.push(next_idxes[i]);
+                prev = Some(idx);
+            }
+            walks.push(walk);
+        }
+    }
+    Ok(walks)
+}
+
+// 返回的节点 index 从小到大排列
+pub(super) fn sample_nodes<G: Adjacency + ?Sized>(graph: &G, k: usize, seed: u64) -> Vec<usize> {
+    let mut res = Rng::new(seed).sample(graph.node_count(), k);
+    res.sort_unstable();
+    res
+}
+
+// 返回的边按 (src_idx, dst_idx) 排列
+pub(super) fn sample_edges<G: Adjacency + ?Sized>(graph: &G, k: usize, seed: u64) -> Vec<(usize, usize)> {
+    let edges = graph.get_all_edges();
+    let mut res: Vec<(usize, usize)> = Rng::new(seed).sample(edges.len(), k).into_iter().map(|i| edges[i]).collect();
+    res.sort_unstable();
+    res
+}
+
+// 返回采到的节点，按采到的先后排列，seeds 在最前面
+pub(super) fn snowball_sample<G: Adjacency + ?Sized>(graph: &G, seeds: &[usize], fanout: usize, max_nodes: usize, seed: u64) -> Result<Vec<usize>> {
+    for &idx in seeds {
+        adjacency::check_idx(graph, idx)?;
+    }
+    let adjacency = sorted_adjacency(graph);
+    let mut rng = Rng::new(seed);
+    let mut sampled = HashSet::new();
+    let mut res = Vec::new();
+    for &idx in seeds {
+        if res.len() < max_nodes && sampled.insert(idx) {
+            res.push(idx);
+        }
+    }
+    let mut frontier = res.clone();
+    while !frontier.is_empty() && res.len() < max_nodes {
+        let mut next_frontier = Vec::new();
+        for idx in frontier {
+            let candidates: Vec<usize> = adjacency[idx].iter().copied()
+                .filter(|next_idx| !sampled.contains(next_idx))
+                .collect();
+            for i in rng.sample(candidates.len(), fanout) {
+                if res.len() == max_nodes {
+                    return Ok(res);
+                }
+                sampled.insert(candidates[i]);
+                res.push(candidates[i]);
+                next_frontier.push(candidates[i]);
+            }
+        }
+        frontier = next_frontier;
+    }
+    Ok(res)
+}
+
+
+impl<'a, K, T> Graph<'a, K, T> {
+    pub fn random_walk_names(&self, starts: &[usize], weight: impl Fn(usize, usize) -> f64, opts: &WalkOptions) -> Result<Vec<Vec<Option<&K>>>> {
+        let walks = self.random_walks(starts, weight, opts)?;
+        Ok(walks.iter().map(|walk| self.get_names_by_idxes(walk)).collect())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph, WalkOptions};
+    use crate::Error;
+
+    #[test]
+    fn test_random_walks() {
+        // A -> B, A -> C, B -> A, B -> C, C -> D（D 没有出边）
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("A", "C"), ("B", "A"), ("B", "C"), ("C", "D")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let [a, b, c, d] = ["A", "B", "C", "D"].map(|name| *graph.get_idx_by_name(name).unwrap());
+        let edges = graph.get_all_edges();
+
+        let opts = WalkOptions { seed: 3, walk_length: 6, walks_per_node: 20, ..Default::default() };
+        let walks = graph.random_walks(&[a, b], |_, _| 1.0, &opts).unwrap();
+        assert_eq!(walks.len(), 40);
+        for walk in &walks {
+            assert!(walk.windows(2).all(|pair| edges.contains(&(pair[0], pair[1]))));
+            // 要么走满，要么停在没有出边的 D
+            assert!(walk.len() == 6 || *walk.last().unwrap() == d);
+        }
+        // 相同的 seed 结果相同
+        assert_eq!(graph.random_walks(&[a, b], |_, _| 1.0, &opts).unwrap(), walks);
+
+        // 带权：A -> C 权重为 0，从 A 出发第一步只能到 B
+        let weights = HashMap::from([((a, b), 1.0), ((a, c), 0.0), ((b, a), 1.0), ((b, c), 1.0), ((c, d), 1.0)]);
+        let walks = graph.random_walk_names(&[a], |s, d| weights[&(s, d)], &opts).unwrap();
+        assert!(walks.iter().all(|walk| walk[1] == Some(&"B")));
+
+        // p 很大：从 B 走到 A 后几乎不会再回到 B，只去 C
+        let opts = WalkOptions { walk_length: 3, p: 1e9, ..opts };
+        let walks = graph.random_walks(&[b], |_, _| 1.0, &opts).unwrap();
+        assert!(walks.iter().filter(|walk| walk[1] == a).all(|walk| walk[2] == c));
+
+        // 重启概率为 1：离开起点后的下一步一定回到起点
+        let opts = WalkOptions { restart_prob: 1.0, ..opts };
+        assert!(graph.random_walks(&[a], |_, _| 1.0, &opts).unwrap().iter().all(|walk| walk[2] == a));
+
+        // 采样
+        assert_eq!(graph.sample_nodes(10, 1), vec![0, 1, 2, 3]);
+        let sampled = graph.sample_edges(3, 1);
+        assert_eq!(sampled.len(), 3);
+        assert!(sampled.iter().all(|edge| edges.contains(edge)));
+        let snowball = graph.snowball_sample(&[b], 2, 10, 5).unwrap();
+        assert_eq!(snowball[0], b);
+        assert_eq!(snowball.len(), 4);
+        assert_eq!(graph.snowball_sample(&[b], 2, 2, 5).unwrap().len(), 2);
+        assert!(graph.random_walks(&[100], |_, _| 1.0, &opts).is_err());
+
+        // 参数不合法
+        for (bad, name) in [
+            (WalkOptions { p: 0.0, ..Default::default() }, "p"),
+            (WalkOptions { q: -1.0, ..Default::default() }, "q"),
+            (WalkOptions { q: f64::INFINITY, ..Default::default() }, "q"),
+            (WalkOptions { restart_prob: 1.5, ..Default::default() }, "restart_prob"),
+            (WalkOptions { restart_prob: f64::NAN, ..Default::default() }, "restart_prob"),
+        ] {
+            assert_eq!(graph.random_walks(&[a], |_, _| 1.0, &bad), Err(Error::InvalidParameter(name)));
+        }
+
+        // 冻结后，相同的 seed 结果相同
+        let opts = WalkOptions { seed: 3, walk_length: 6, walks_per_node: 20, ..Default::default() };
+        let expected = (graph.random_walks(&[a, b], |_, _| 1.0, &opts).unwrap(), graph.sample_edges(3, 1), graph.snowball_sample(&[b], 2, 10, 5).unwrap());
+        let frozen = arena_list.freeze();
+        assert_eq!((frozen.random_walks(&[a, b], |_, _| 1.0, &opts).unwrap(), frozen.sample_edges(3, 1), frozen.snowball_sample(&[b], 2, 10, 5).unwrap()), expected);
+    }
+}
@@ -0,0 +1,30 @@
+/*
+BinaryHeap 是大顶堆，f64 也没有实现 Ord
+MinScored 按 score 反向比较（用 total_cmp），放进 BinaryHeap 后每次弹出 score 最小的元素
+Prim、A*、Dijkstra 等算法共用
+*/
+
+use std::cmp::Ordering;
+
+
+pub(crate) struct MinScored<S>(pub f64, pub S);
+
+impl<S> PartialEq for MinScored<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for MinScored<S> {}
+
+impl<S> PartialOrd for MinScored<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for MinScored<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
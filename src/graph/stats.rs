@@ -0,0 +1,291 @@
+/*
+结构统计，除度数分布外都把有向边看作无向边，忽略自环（A->B 与 B->A 算同一条边）
+- core_numbers：每个节点的 k-core 编号（所在的最大 k-core 的 k），Batagelj-Zaversnik 桶排序，O(n + m)
+  k_core(k)：core 编号不小于 k 的节点
+- triangle_counts：每个节点参与的三角形个数；triangle_count：三角形总数
+  按 (度数, index) 给边定向，只从排名低的一端往高的一端找，O(m^1.5)，度数很大的节点不会拖慢速度
+- local_clustering：局部聚类系数 2T / (d(d - 1))，度数小于 2 的节点为 0
+  average_clustering：局部聚类系数的平均值
+  global_clustering：全局聚类系数（transitivity），3 × 三角形数 / 连通三元组数
+- degree_histogram(direction)：hist[d] 为度数等于 d 的节点个数，
+  Outgoing 为出度、Incoming 为入度（自环都计入），Both 为无向邻居数（不含自身）
+邻接表都转成排好序的 Vec，不在 HashSet 上反复查询，适合百万条边的图
+只用到 successors / predecessors，写在 Adjacency 上，Graph 和 FrozenGraph 都可以使用
+*/
+
+use super::{Adjacency, Direction};
+
+
+// 只在本模块用到：去掉自环后的无向邻居（successors 和 predecessors 的并集），从小到大排列
+fn simple_neighbors<G: Adjacency + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    (0..graph.node_count())
+        .map(|idx| {
+            let mut neighbors: Vec<usize> = graph.successors(idx).chain(graph.predecessors(idx))
+                .filter(|&next_idx| next_idx != idx)
+                .collect();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            neighbors
+        })
+        .collect()
+}
+
+
+pub(super) fn core_numbers<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let neighbors = simple_neighbors(graph);
+    let n = neighbors.len();
+    let mut degree: Vec<usize> = neighbors.iter().map(|next_idxes| next_idxes.len()).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    // 按度数桶排序：vert 为排好序的节点，pos[idx] 为节点在 vert 中的位置，bin[d] 为度数 d 的起始位置
+    let mut bin = vec![0; max_degree + 1];
+    for &d in &degree {
+        bin[d] += 1;
+    }
+    let mut start = 0;
+    for count in bin.iter_mut() {
+        let len = *count;
+        *count = start;
+        start += len;
+    }
+    let mut pos = vec![0; n];
+    let mut vert = vec![0; n];
+    for idx in 0..n {
+        pos[idx] = bin[degree[idx]];
+        vert[pos[idx]] = idx;
+        bin[degree[idx]] += 1;
+    }
+    for d in (1..=max_degree).rev() {
+        bin[d] = bin[d - 1];
+    }
+    bin[0] = 0;
+    // 依次剥掉度数最小的节点，邻居度数减 1 时与所在桶的第一个节点交换位置
+    for i in 0..n {
+        let idx = vert[i];
+        for &next_idx in &neighbors[idx] {
+            if degree[next_idx] > degree[idx] {
+                let d = degree[next_idx];
+                let first = vert[bin[d]];
+                if first != next_idx {
+                    vert.swap(pos[next_idx], bin[d]);
+                    pos[first] = pos[next_idx];
+                    pos[next_idx] = bin[d];
+                }
+                bin[d] += 1;
+                degree[next_idx] -= 1;
+            }
+        }
+    }
+    degree
+}
+
+
+// 从小到大排列
+pub(super) fn k_core<G: Adjacency + ?Sized>(graph: &G, k: usize) -> Vec<usize> {
+    core_numbers(graph).into_iter().enumerate()
+        .filter(|&(_, core)| core >= k)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+
+pub(super) fn triangle_counts<G: Adjacency + ?Sized>(graph: &G) -> Vec<usize> {
+    let neighbors = simple_neighbors(graph);
+    let n = neighbors.len();
+    let rank = |idx: usize| (neighbors[idx].len(), idx);
+    // 只保留排名更高的邻居
+    let higher: Vec<Vec<usize>> = neighbors.iter().enumerate()
+        .map(|(idx, next_idxes)| next_idxes.iter().copied().filter(|&next_idx| rank(next_idx) > rank(idx)).collect())
+        .collect();
+    let mut counts = vec![0; n];
+    let mut marked = vec![false; n];
+    for u in 0..n {
+        for &v in &higher[u] {
+            marked[v] = true;
+        }
+        for &v in &higher[u] {
+            for &w in &higher[v] {
+                if marked[w] {
+                    counts[u] += 1;
+                    counts[v] += 1;
+                    counts[w] += 1;
+                }
+            }
+        }
+        for &v in &higher[u] {
+            marked[v] = false;
+        }
+    }
+    counts
+}
+
+
+pub(super) fn triangle_count<G: Adjacency + ?Sized>(graph: &G) -> usize {
+    triangle_counts(graph).iter().sum::<usize>() / 3
+}
+
+
+pub(super) fn local_clustering<G: Adjacency + ?Sized>(graph: &G) -> Vec<f64> {
+    let triangles = triangle_counts(graph);
+    simple_neighbors(graph).iter().zip(triangles)
+        .map(|(next_idxes, t)| {
+            let d = next_idxes.len();
+            if d < 2 { 0.0 } else { 2.0 * t as f64 / (d * (d - 1)) as f64 }
+        })
+        .collect()
+}
+
+
+// 空图为 0
+pub(super) fn average_clustering<G: Adjacency + ?Sized>(graph: &G) -> f64 {
+    let local = local_clustering(graph);
+    if local.is_empty() {
+        return 0.0;
+    }
+    local.iter().sum::<f64>() / local.len() as f64
+}
+
+
+// 没有连通三元组时为 0
+pub(super) fn global_clustering<G: Adjacency + ?Sized>(graph: &G) -> f64 {
+    let triangles: usize = triangle_counts(graph).iter().sum();
+    let triples: usize = simple_neighbors(graph).iter()
+        .map(|next_idxes| next_idxes.len() * next_idxes.len().saturating_sub(1) / 2)
+        .sum();
+    if triples == 0 { 0.0 } else { triangles as f64 / triples as f64 }
+}
+
+
+pub(super) fn degree_histogram<G: Adjacency + ?Sized>(graph: &G, direction: Direction) -> Vec<usize> {
+    let degrees: Vec<usize> = match direction {
+        Direction::Outgoing => (0..graph.node_count()).map(|idx| graph.successors(idx).count()).collect(),
+        Direction::Incoming => (0..graph.node_count()).map(|idx| graph.predecessors(idx).count()).collect(),
+        Direction::Both => simple_neighbors(graph).iter().map(|next_idxes| next_idxes.len()).collect(),
+    };
+    let mut hist = vec![0; degrees.iter().copied().max().map_or(0, |d| d + 1)];
+    for d in degrees {
+        hist[d] += 1;
+    }
+    hist
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Rng;
+    use crate::graph::{Adjacency, ArenaList, Direction, Graph};
+
+    #[test]
+    fn test_structure_stats() {
+        // A、B、C、D 两两相连（4-clique，部分边双向），E 挂在 D 上，F 挂在 E 上，G 孤立且有自环
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("B", "A"), ("A", "C"), ("A", "D"), ("B", "C"), ("D", "B"), ("C", "D"), ("D", "E"), ("E", "F"), ("G", "G")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let idx = |name| *graph.get_idx_by_name(name).unwrap();
+        let [a, d, e, f, g] = ["A", "D", "E", "F", "G"].map(idx);
+
+        let core = graph.core_numbers();
+        assert_eq!((core[a], core[d], core[e], core[f], core[g]), (3, 3, 1, 1, 0));
+        assert_eq!(graph.k_core(3).len(), 4);
+
+        let triangles = graph.triangle_counts();
+        assert_eq!((triangles[a], triangles[d], triangles[e]), (3, 3, 0));
+        assert_eq!(graph.triangle_count(), 4);
+
+        let local = graph.local_clustering();
+        assert_eq!(local[a], 1.0);
+        // D 的邻居 A、B、C、E：3 个三角形 / 6 对
+        assert_eq!(local[d], 0.5);
+        assert_eq!(local[f], 0.0);
+        // 三元组：A、B、C 各 3 个，D 6 个，E 1 个
+        assert!((graph.global_clustering() - 12.0 / 16.0).abs() < 1e-12);
+        assert!((graph.average_clustering() - 3.5 / 7.0).abs() < 1e-12);
+
+        assert_eq!(graph.degree_histogram(Direction::Both), vec![1, 1, 1, 3, 1]);
+        assert_eq!(graph.degree_histogram(Direction::Outgoing), vec![1, 3, 2, 1]);
+        assert_eq!(graph.degree_histogram(Direction::Incoming), vec![0, 4, 3]);
+
+        // 冻结后结果相同
+        let expected = (core, triangles, local, graph.degree_histogram(Direction::Outgoing));
+        let frozen = arena_list.freeze();
+        assert_eq!((frozen.core_numbers(), frozen.triangle_counts(), frozen.local_clustering(), frozen.degree_histogram(Direction::Outgoing)), expected);
+        assert_eq!(frozen.degree_histogram(Direction::Both), vec![1, 1, 1, 3, 1]);
+        assert_eq!(frozen.triangle_count(), 4);
+    }
+
+    #[test]
+    fn test_structure_stats_edge_cases() {
+        let mut arena_list: ArenaList<&str, ()> = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        // 空图
+        assert!(graph.core_numbers().is_empty());
+        assert_eq!(graph.triangle_count(), 0);
+        assert_eq!(graph.average_clustering(), 0.0);
+        assert_eq!(graph.global_clustering(), 0.0);
+        assert!(graph.degree_histogram(Direction::Both).is_empty());
+
+        // 只有自环：无向度数为 0，有向度数都计入自环
+        graph.add_node_and_edge("A", (), "A", ());
+        assert_eq!(graph.core_numbers(), vec![0]);
+        assert_eq!(graph.degree_histogram(Direction::Both), vec![1]);
+        assert_eq!(graph.degree_histogram(Direction::Outgoing), vec![0, 1]);
+
+        // 两个不相连的三角形，一个有向一个双向，结果相同
+        for (src_name, dst_name) in [("B", "C"), ("C", "D"), ("D", "B"), ("E", "F"), ("F", "E"), ("F", "G"), ("G", "F"), ("G", "E"), ("E", "G")] {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        assert_eq!(graph.triangle_counts(), vec![0, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(graph.k_core(2), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(graph.global_clustering(), 1.0);
+        assert!((graph.average_clustering() - 6.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_structure_stats_random() {
+        // 随机图，与按邻接矩阵逐个枚举的结果对照
+        let mut rng = Rng::new(9);
+        for _ in 0..20 {
+            let n = 1 + rng.gen_range(12);
+            let mut arena_list = ArenaList::new();
+            let mut graph = Graph::new(&mut arena_list);
+            for i in 0..n {
+                graph.add_node(i, ());
+            }
+            for _ in 0..rng.gen_range(40) {
+                graph.add_edge(rng.gen_range(n), rng.gen_range(n));
+            }
+            let mut adj = vec![vec![false; n]; n];
+            for (src_idx, dst_idx) in graph.get_all_edges() {
+                if src_idx != dst_idx {
+                    adj[src_idx][dst_idx] = true;
+                    adj[dst_idx][src_idx] = true;
+                }
+            }
+            let degree: Vec<usize> = adj.iter().map(|row| row.iter().filter(|&&e| e).count()).collect();
+
+            let triangles: Vec<usize> = (0..n)
+                .map(|u| (0..n).flat_map(|v| (v + 1..n).map(move |w| (v, w))).filter(|&(v, w)| adj[u][v] && adj[u][w] && adj[v][w]).count())
+                .collect();
+            assert_eq!(graph.triangle_counts(), triangles);
+            let local: Vec<f64> = (0..n)
+                .map(|u| if degree[u] < 2 { 0.0 } else { 2.0 * triangles[u] as f64 / (degree[u] * (degree[u] - 1)) as f64 })
+                .collect();
+            assert!(graph.local_clustering().iter().zip(&local).all(|(a, b)| (a - b).abs() < 1e-12));
+            let triples: usize = degree.iter().map(|&d| d * d.saturating_sub(1) / 2).sum();
+            let global = if triples == 0 { 0.0 } else { triangles.iter().sum::<usize>() as f64 / triples as f64 };
+            assert!((graph.global_clustering() - global).abs() < 1e-12);
+
+            // k-core：反复删掉度数小于 k 的节点，剩下的就是 core 编号不小于 k 的节点
+            let core = graph.core_numbers();
+            for k in 0..=n {
+                let mut alive = vec![true; n];
+                while let Some(u) = (0..n).find(|&u| alive[u] && (0..n).filter(|&v| alive[v] && adj[u][v]).count() < k) {
+                    alive[u] = false;
+                }
+                assert!((0..n).all(|u| alive[u] == (core[u] >= k)));
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/*
+子图提取，结果放进新的 ArenaList，节点名、数据都克隆一份，原图不变
+- induced_subgraph(node_set)：选中的节点，以及它们之间的全部边
+  例如把 get_downstream 的结果单独拿出来：levels.values().flatten() 作为 node_set
+- ego_graph(center, radius, direction)：离 center 不超过 radius 跳的节点（含 center）构成的导出子图
+  注意 radius 包含端点：radius = 1 时包含直接相邻的节点（get_downstream 的 max_level = 1 只有起点本身）
+- 同时返回 {旧 index: 新 index}，新 index 按 node_set 中（或 BFS 中）首次出现的顺序分配
+*/
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use crate::error::Result;
+use super::{ArenaList, Graph};
+
+
+// ego_graph 沿哪个方向扩展
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    // 沿 next_idx，下游
+    Outgoing,
+    // 沿 prev_idx，上游
+    Incoming,
+    // 两个方向都走，即把边看作无向边
+    Both,
+}
+
+
+impl<'a, K: Hash + Eq + Clone, T: Clone> Graph<'a, K, T> {
+    pub fn induced_subgraph(&self, node_set: &[usize]) -> Result<(ArenaList<K, T>, HashMap<usize, usize>)> {
+        let mut idx_map = HashMap::new();
+        let mut arena_list = ArenaList::new();
+        for &idx in node_set {
+            let node = self.try_get_node_by_idx(idx)?;
+            if idx_map.contains_key(&idx) {
+                continue;
+            }
+            let new_idx = match &node.name {
+                Some(name) => arena_list.add_node(name.clone(), node.data.clone()),
+                None => arena_list.add_anonymous_node(node.data.clone()),
+            };
+            idx_map.insert(idx, new_idx);
+        }
+        for (&idx, &new_idx) in &idx_map {
+            for next_idx in &self.owner.nodes[idx].next_idx {
+                if let Some(&new_next_idx) = idx_map.get(next_idx) {
+                    arena_list.add_edge(new_idx, new_next_idx);
+                }
+            }
+        }
+        Ok((arena_list, idx_map))
+    }
+
+    pub fn ego_graph(&self, center: usize, radius: usize, direction: Direction) -> Result<(ArenaList<K, T>, HashMap<usize, usize>)> {
+        self.try_get_node_by_idx(center)?;
+        let nodes = &self.owner.nodes;
+        let mut dist = HashMap::from([(center, 0)]);
+        let mut order = vec![center];
+        let mut q = VecDeque::from([center]);
+        while let Some(idx) = q.pop_front() {
+            if dist[&idx] == radius {
+                continue;
+            }
+            let (next_idx, prev_idx) = (&nodes[idx].next_idx, &nodes[idx].prev_idx);
+            let sets = match direction {
+                Direction::Outgoing => [Some(next_idx), None],
+                Direction::Incoming => [None, Some(prev_idx)],
+                Direction::Both => [Some(next_idx), Some(prev_idx)],
+            };
+            for &next_idx in sets.into_iter().flatten().flatten() {
+                if !dist.contains_key(&next_idx) {
+                    dist.insert(next_idx, dist[&idx] + 1);
+                    order.push(next_idx);
+                    q.push_back(next_idx);
+                }
+            }
+        }
+        self.induced_subgraph(&order)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Direction, Graph};
+
+    #[test]
+    fn test_subgraph() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("B", "C"), ("C", "D"), ("B", "E"), ("X", "B"), ("E", "A")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, src_name.to_lowercase(), dst_name, dst_name.to_lowercase());
+        }
+        let [a, b, c, d, e, x] = ["A", "B", "C", "D", "E", "X"].map(|name| *graph.get_idx_by_name(name).unwrap());
+
+        // 把 get_downstream 的结果单独拿出来
+        let levels = graph.get_downstream(vec![b], 2);
+        let node_set: Vec<usize> = (0..2).flat_map(|level| levels[&level].clone()).collect();
+        let (mut sub_arena_list, idx_map) = graph.induced_subgraph(&node_set).unwrap();
+        let sub = Graph::new(&mut sub_arena_list);
+        assert_eq!(idx_map[&b], 0);
+        assert!(!idx_map.contains_key(&d));
+        assert_eq!(sub.get_all_edges().len(), 2);
+        assert!(sub.get_node_by_idx(idx_map[&b]).next_idx.contains(&idx_map[&c]));
+        assert_eq!(sub.get_node_by_idx(idx_map[&e]).data, "e");
+
+        let (mut ego_arena_list, idx_map) = graph.ego_graph(b, 1, Direction::Incoming).unwrap();
+        let mut ego_names: Vec<&str> = idx_map.keys().map(|&idx| *graph.get_name_by_idx(idx).unwrap()).collect();
+        ego_names.sort();
+        assert_eq!(ego_names, vec!["A", "B", "X"]);
+        assert_eq!(Graph::new(&mut ego_arena_list).get_all_edges().len(), 2);
+
+        let (_, idx_map) = graph.ego_graph(b, 1, Direction::Both).unwrap();
+        assert_eq!(idx_map.len(), 5);
+        let (_, idx_map) = graph.ego_graph(x, 3, Direction::Outgoing).unwrap();
+        assert_eq!(idx_map.len(), 6);
+        assert!(idx_map.contains_key(&a) && idx_map.contains_key(&d));
+        assert!(graph.induced_subgraph(&[100]).is_err());
+    }
+}
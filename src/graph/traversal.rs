@@ -0,0 +1,300 @@
+/*
+通用的深度优先 / 广度优先遍历，写在 Adjacency 上，Graph 和 FrozenGraph 都可以使用
+- dfs(starts) / bfs(starts)：返回事件的迭代器，不递归，深图也不会栈溢出
+- dfs_visit(starts, visitor) / bfs_visit(starts, visitor)：对每个事件调用 visitor，
+  visitor 返回 Control 决定继续、剪枝还是停止，停止时返回触发停止的事件
+- starts 中的节点依次作为起点，已经访问过的跳过；想遍历全图时传入 0..node_count()
+- starts 中有不存在的节点时，dfs / bfs 遍历到它会 panic；try_dfs / try_bfs / try_dfs_visit / try_bfs_visit
+  先检查全部起点，返回 Error::UnknownNode
+
+事件（VisitEvent）
+- Discover(idx)：第一次访问到节点
+- TreeEdge(src, dst)：沿这条边发现了 dst，紧接着是 Discover(dst)
+- BackEdge(src, dst)：dst 还在栈上（DFS），说明有环
+- ForwardOrCrossEdge(src, dst)：dst 已经结束（DFS），前向边或横叉边
+- NonTreeEdge(src, dst)：dst 已经被发现过（BFS 不区分回边和横叉边）
+- Finish(idx)：节点的出边都处理完了
+
+剪枝：prune() 不再扩展最近一次 Discover 的节点，在收到 Discover 或 TreeEdge 时调用即可剪掉这棵子树
+被剪掉的节点仍然会有 Finish 事件
+*/
+
+use std::collections::VecDeque;
+use super::Adjacency;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitEvent {
+    Discover(usize),
+    TreeEdge(usize, usize),
+    BackEdge(usize, usize),
+    ForwardOrCrossEdge(usize, usize),
+    NonTreeEdge(usize, usize),
+    Finish(usize),
+}
+
+
+// visitor 的返回值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    // 不再扩展最近一次 Discover 的节点
+    Prune,
+    // 立即结束遍历
+    Stop,
+}
+
+
+// Dfs、Bfs 共用的剪枝接口，供 drive 使用
+pub(super) trait Prune: Iterator<Item = VisitEvent> {
+    fn prune(&mut self);
+}
+
+impl<G: Adjacency + ?Sized> Prune for Dfs<'_, G> {
+    fn prune(&mut self) { Dfs::prune(self) }
+}
+
+impl<G: Adjacency + ?Sized> Prune for Bfs<'_, G> {
+    fn prune(&mut self) { Bfs::prune(self) }
+}
+
+
+// 节点的状态：还没访问、在栈上（或队列中）、已经结束
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+
+pub struct Dfs<'g, G: Adjacency + ?Sized> {
+    graph: &'g G,
+    starts: VecDeque<usize>,
+    color: Vec<Color>,
+    // (节点, 它的下游, 下一个要处理的位置)
+    stack: Vec<(usize, Vec<usize>, usize)>,
+    // TreeEdge 之后要发出的 Discover
+    pending: Option<usize>,
+}
+
+impl<'g, G: Adjacency + ?Sized> Dfs<'g, G> {
+    pub(super) fn new(graph: &'g G, starts: Vec<usize>) -> Self {
+        Dfs { graph, starts: starts.into(), color: vec![Color::White; graph.node_count()], stack: Vec::new(), pending: None }
+    }
+
+    fn discover(&mut self, idx: usize) {
+        self.color[idx] = Color::Gray;
+        self.stack.push((idx, self.graph.successors(idx).collect(), 0));
+    }
+
+    // 最近一次 Discover 的节点就在栈顶，并且还没有处理过它的出边
+    pub fn prune(&mut self) {
+        if let Some((_, next_idxes, pos)) = self.stack.last_mut() {
+            if *pos == 0 {
+                next_idxes.clear();
+            }
+        }
+    }
+}
+
+impl<'g, G: Adjacency + ?Sized> Iterator for Dfs<'g, G> {
+    type Item = VisitEvent;
+
+    fn next(&mut self) -> Option<VisitEvent> {
+        if let Some(idx) = self.pending.take() {
+            return Some(VisitEvent::Discover(idx));
+        }
+        if let Some((idx, next_idxes, pos)) = self.stack.last_mut() {
+            let idx = *idx;
+            let Some(&next_idx) = next_idxes.get(*pos) else {
+                self.stack.pop();
+                self.color[idx] = Color::Black;
+                return Some(VisitEvent::Finish(idx));
+            };
+            *pos += 1;
+            return Some(match self.color[next_idx] {
+                Color::White => {
+                    self.discover(next_idx);
+                    self.pending = Some(next_idx);
+                    VisitEvent::TreeEdge(idx, next_idx)
+                }
+                Color::Gray => VisitEvent::BackEdge(idx, next_idx),
+                Color::Black => VisitEvent::ForwardOrCrossEdge(idx, next_idx),
+            });
+        }
+        // 栈空了，换下一个起点
+        while let Some(idx) = self.starts.pop_front() {
+            if self.color[idx] == Color::White {
+                self.discover(idx);
+                return Some(VisitEvent::Discover(idx));
+            }
+        }
+        None
+    }
+}
+
+
+pub struct Bfs<'g, G: Adjacency + ?Sized> {
+    graph: &'g G,
+    starts: VecDeque<usize>,
+    color: Vec<Color>,
+    q: VecDeque<usize>,
+    // 正在处理的节点：(节点, 它的下游, 下一个要处理的位置)
+    current: Option<(usize, Vec<usize>, usize)>,
+    pending: Option<usize>,
+    // 被剪枝的节点，出队时不展开
+    pruned: Vec<bool>,
+    last_discovered: Option<usize>,
+}
+
+impl<'g, G: Adjacency + ?Sized> Bfs<'g, G> {
+    pub(super) fn new(graph: &'g G, starts: Vec<usize>) -> Self {
+        let n = graph.node_count();
+        Bfs {
+            graph,
+            starts: starts.into(),
+            color: vec![Color::White; n],
+            q: VecDeque::new(),
+            current: None,
+            pending: None,
+            pruned: vec![false; n],
+            last_discovered: None,
+        }
+    }
+
+    fn discover(&mut self, idx: usize) {
+        self.color[idx] = Color::Gray;
+        self.q.push_back(idx);
+        self.last_discovered = Some(idx);
+    }
+
+    pub fn prune(&mut self) {
+        if let Some(idx) = self.last_discovered {
+            self.pruned[idx] = true;
+        }
+    }
+}
+
+impl<'g, G: Adjacency + ?Sized> Iterator for Bfs<'g, G> {
+    type Item = VisitEvent;
+
+    fn next(&mut self) -> Option<VisitEvent> {
+        if let Some(idx) = self.pending.take() {
+            return Some(VisitEvent::Discover(idx));
+        }
+        loop {
+            if let Some((idx, next_idxes, pos)) = &mut self.current {
+                let idx = *idx;
+                let Some(&next_idx) = next_idxes.get(*pos) else {
+                    self.current = None;
+                    self.color[idx] = Color::Black;
+                    return Some(VisitEvent::Finish(idx));
+                };
+                *pos += 1;
+                if self.color[next_idx] == Color::White {
+                    self.discover(next_idx);
+                    self.pending = Some(next_idx);
+                    return Some(VisitEvent::TreeEdge(idx, next_idx));
+                }
+                return Some(VisitEvent::NonTreeEdge(idx, next_idx));
+            }
+            if let Some(idx) = self.q.pop_front() {
+                let next_idxes = if self.pruned[idx] { Vec::new() } else { self.graph.successors(idx).collect() };
+                self.current = Some((idx, next_idxes, 0));
+                continue;
+            }
+            // 队列空了，换下一个起点
+            let idx = self.starts.pop_front()?;
+            if self.color[idx] == Color::White {
+                self.discover(idx);
+                return Some(VisitEvent::Discover(idx));
+            }
+        }
+    }
+}
+
+
+// dfs_visit / bfs_visit 共用：逐个事件调用 visitor，返回触发 Stop 的事件
+pub(super) fn drive<I: Prune>(mut iter: I, mut visitor: impl FnMut(VisitEvent) -> Control) -> Option<VisitEvent> {
+    while let Some(event) = iter.next() {
+        match visitor(event) {
+            Control::Continue => {}
+            Control::Prune => iter.prune(),
+            Control::Stop => return Some(event),
+        }
+    }
+    None
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Adjacency, ArenaList, Control, Graph, VisitEvent};
+
+    #[test]
+    fn test_traversal() {
+        // A -> B -> C -> A（环），A -> D，B -> D，D -> E
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![("A", "B"), ("B", "C"), ("C", "A"), ("A", "D"), ("B", "D"), ("D", "E")];
+        for (src_name, dst_name) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        let [a, b, c, d, e] = ["A", "B", "C", "D", "E"].map(|name| *graph.get_idx_by_name(name).unwrap());
+
+        // 每个节点恰好 Discover、Finish 一次，括号结构正确，每条边恰好分类一次
+        let events: Vec<VisitEvent> = graph.dfs(vec![a]).collect();
+        let discovered: Vec<usize> = events.iter().filter_map(|event| match event { VisitEvent::Discover(idx) => Some(*idx), _ => None }).collect();
+        assert_eq!(discovered.len(), 5);
+        assert_eq!(discovered[0], a);
+        let mut open = Vec::new();
+        for event in &events {
+            match *event {
+                VisitEvent::Discover(idx) => open.push(idx),
+                VisitEvent::Finish(idx) => assert_eq!(open.pop(), Some(idx)),
+                _ => {}
+            }
+        }
+        let edge_events = events.iter().filter(|event| !matches!(event, VisitEvent::Discover(_) | VisitEvent::Finish(_))).count();
+        assert_eq!(edge_events, 6);
+        assert!(events.contains(&VisitEvent::BackEdge(c, a)));
+        assert_eq!(events.iter().filter(|event| matches!(event, VisitEvent::TreeEdge(..))).count(), 4);
+
+        // 剪掉 B：C 不会被发现，D、E 仍可以从 A 到达
+        let mut seen = Vec::new();
+        graph.dfs_visit(vec![a], |event| match event {
+            VisitEvent::Discover(idx) => {
+                seen.push(idx);
+                if idx == b { Control::Prune } else { Control::Continue }
+            }
+            _ => Control::Continue,
+        });
+        seen.sort();
+        let mut expected = vec![a, b, d, e];
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        // BFS 按层发现；找到 E 就停止
+        let order: Vec<usize> = graph.bfs(vec![a]).filter_map(|event| match event { VisitEvent::Discover(idx) => Some(idx), _ => None }).collect();
+        // 第 0 层 A，第 1 层 B、D，第 2 层 C、E（同一层内的顺序不固定）
+        assert_eq!(order[0], a);
+        let mut level1 = order[1..3].to_vec();
+        level1.sort();
+        let mut expected = vec![b, d];
+        expected.sort();
+        assert_eq!(level1, expected);
+        let stopped = graph.bfs_visit(vec![a], |event| if event == VisitEvent::Discover(e) { Control::Stop } else { Control::Continue });
+        assert_eq!(stopped, Some(VisitEvent::Discover(e)));
+        assert_eq!(graph.dfs_visit(vec![c], |_| Control::Continue), None);
+
+        // 起点不存在：先检查，一个事件都不产生
+        assert!(graph.try_dfs(vec![a, 100]).is_err());
+        assert!(graph.try_bfs(vec![100]).is_err());
+        let mut called = false;
+        assert!(graph.try_bfs_visit(vec![a, 100], |_| { called = true; Control::Continue }).is_err());
+        assert!(!called);
+        assert_eq!(graph.try_dfs(vec![a]).unwrap().count(), events.len());
+        assert_eq!(graph.try_dfs_visit(vec![c], |_| Control::Continue).unwrap(), None);
+    }
+}
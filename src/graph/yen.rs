@@ -0,0 +1,152 @@
+/*
+Yen 算法：两个节点之间前 k 条最短的简单路径（不含重复节点），按总代价从小到大排列
+- 边权由 weight(src_idx, dst_idx) 给出，需非负；只看跳数时传入 |_, _| 1.0
+- 第 i 条路径确定后，依次以它上面的每个节点为偏离点（spur）：
+  屏蔽偏离点之前的节点，以及与已有路径共用前缀时的下一条边，再用 dijkstra 求偏离点到终点的最短路，
+  拼上前缀得到候选路径。所有候选放进小顶堆，每次取代价最小的一条
+- 屏蔽通过把边权改为 f64::INFINITY 实现，不修改原图
+- 可达的简单路径不足 k 条时，返回全部
+k_shortest_paths 只依赖拓扑结构，写在 Adjacency 上，FrozenGraph 也能用；k_shortest_paths_by_name 只在 Graph 上
+*/
+
+use std::borrow::Borrow;
+use std::collections::{BinaryHeap, HashSet};
+use std::hash::Hash;
+use crate::error::Result;
+use super::adjacency::{self, Adjacency};
+use super::dijkstra::dijkstra;
+use super::scored::MinScored;
+use super::Graph;
+
+
+// 返回 [(路径上的节点 index, 总代价)]，路径包含起点和终点
+pub(super) fn k_shortest_paths<G: Adjacency + ?Sized>(graph: &G, src_idx: usize, dst_idx: usize, k: usize, weight: impl Fn(usize, usize) -> f64) -> Result<Vec<(Vec<usize>, f64)>> {
+    adjacency::check_idx(graph, src_idx)?;
+    adjacency::check_idx(graph, dst_idx)?;
+    let mut res: Vec<(Vec<usize>, f64)> = Vec::new();
+    if k == 0 {
+        return Ok(res);
+    }
+    match dijkstra(graph, src_idx, &weight)?.path_to(dst_idx) {
+        Some(path) => {
+            let cost = path_cost(&path, &weight);
+            res.push((path, cost));
+        }
+        None => return Ok(res),
+    }
+
+    let mut candidates = BinaryHeap::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::from([res[0].0.clone()]);
+    while res.len() < k {
+        let last = res.last().unwrap().0.clone();
+        for i in 0..last.len() - 1 {
+            let (spur_idx, root) = (last[i], &last[..=i]);
+            let blocked_edges: HashSet<(usize, usize)> = res.iter()
+                .filter(|(path, _)| path.len() > i + 1 && &path[..=i] == root)
+                .map(|(path, _)| (path[i], path[i + 1]))
+                .collect();
+            let blocked_nodes: HashSet<usize> = root[..i].iter().copied().collect();
+            let spur_weight = |src_idx, dst_idx| {
+                if blocked_edges.contains(&(src_idx, dst_idx)) || blocked_nodes.contains(&dst_idx) {
+                    f64::INFINITY
+                } else {
+                    weight(src_idx, dst_idx)
+                }
+            };
+            let spur_paths = dijkstra(graph, spur_idx, spur_weight)?;
+            if let Some(spur_path) = spur_paths.path_to(dst_idx) {
+                let mut path = root[..i].to_vec();
+                path.extend(spur_path);
+                if seen.insert(path.clone()) {
+                    candidates.push(MinScored(path_cost(&path, &weight), path));
+                }
+            }
+        }
+        match candidates.pop() {
+            Some(MinScored(cost, path)) => res.push((path, cost)),
+            None => break,
+        }
+    }
+    Ok(res)
+}
+
+// [(路径上的节点名, 总代价)]
+type NamedPaths<'b, K> = Vec<(Vec<Option<&'b K>>, f64)>;
+
+impl<'a, K: Hash + Eq, T> Graph<'a, K, T> {
+    // 按节点名查询，路径也转为节点名（匿名节点为 None）
+    pub fn k_shortest_paths_by_name<Q>(&self, src_name: &Q, dst_name: &Q, k: usize, weight: impl Fn(usize, usize) -> f64) -> Result<NamedPaths<'_, K>>
+    where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let src_idx = self.try_get_idx_by_name(src_name)?;
+        let dst_idx = self.try_get_idx_by_name(dst_name)?;
+        Ok(self.k_shortest_paths(src_idx, dst_idx, k, weight)?.into_iter()
+            .map(|(path, cost)| (self.get_names_by_idxes(&path), cost))
+            .collect())
+    }
+}
+
+
+fn path_cost(path: &[usize], weight: impl Fn(usize, usize) -> f64) -> f64 {
+    path.windows(2).map(|pair| weight(pair[0], pair[1])).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::graph::{Adjacency, ArenaList, Graph};
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        let vec1 = vec![
+            ("C", "D", 3.0), ("C", "E", 2.0), ("D", "F", 4.0), ("E", "D", 1.0), ("E", "F", 2.0),
+            ("E", "G", 3.0), ("F", "G", 2.0), ("F", "H", 1.0), ("G", "H", 2.0)];
+        let mut weights = HashMap::new();
+        for (src_name, dst_name, w) in vec1 {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+            weights.insert((*graph.get_idx_by_name(src_name).unwrap(), *graph.get_idx_by_name(dst_name).unwrap()), w);
+        }
+        let weight = |src_idx, dst_idx| weights[&(src_idx, dst_idx)];
+
+        let paths = graph.k_shortest_paths_by_name("C", "H", 3, weight).unwrap();
+        assert_eq!(paths[0], (vec![Some(&"C"), Some(&"E"), Some(&"F"), Some(&"H")], 5.0));
+        assert_eq!(paths[1], (vec![Some(&"C"), Some(&"E"), Some(&"G"), Some(&"H")], 7.0));
+        // 代价为 8 的路径有三条（C-D-F-H、C-E-D-F-H、C-E-F-G-H），取到哪条都可以
+        assert_eq!(paths[2].1, 8.0);
+
+        // 按跳数，总共只有 7 条简单路径
+        let paths = graph.k_shortest_paths_by_name("C", "H", 100, |_, _| 1.0).unwrap();
+        assert_eq!(paths.len(), 7);
+        assert_eq!(paths[0].1, 3.0);
+        assert!(paths.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert!(graph.k_shortest_paths_by_name("C", "X", 3, weight).is_err());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_edge_cases() {
+        // A -> A（自环）、A -> B、A -> C -> B，D 孤立
+        let mut arena_list = ArenaList::new();
+        let mut graph = Graph::new(&mut arena_list);
+        for (src_name, dst_name) in [("A", "A"), ("A", "B"), ("A", "C"), ("C", "B")] {
+            graph.add_node_and_edge(src_name, (), dst_name, ());
+        }
+        graph.add_node("D", ());
+        let [a, b, d] = ["A", "B", "D"].map(|name| *graph.get_idx_by_name(name).unwrap());
+
+        assert!(graph.k_shortest_paths(a, b, 0, |_, _| 1.0).unwrap().is_empty());
+        assert!(graph.k_shortest_paths(a, d, 5, |_, _| 1.0).unwrap().is_empty());
+        assert_eq!(graph.k_shortest_paths(a, a, 5, |_, _| 1.0).unwrap(), vec![(vec![a], 0.0)]);
+        // 自环不会出现在简单路径中，只有两条
+        let paths = graph.k_shortest_paths(a, b, 5, |_, _| 1.0).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0], (vec![a, b], 1.0));
+        assert!(graph.k_shortest_paths(a, 100, 1, |_, _| 1.0).is_err());
+        assert!(graph.k_shortest_paths_by_name("A", "X", 1, |_, _| 1.0).is_err());
+
+        // 冻结后结果相同
+        let frozen = arena_list.freeze();
+        assert_eq!(frozen.k_shortest_paths(a, b, 5, |_, _| 1.0).unwrap(), paths);
+    }
+}
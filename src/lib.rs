@@ -1,5 +1,13 @@
-mod linked_list;
+mod error;
+pub mod linked_list;
+pub mod graph;
+pub mod union_find;
+pub mod rng;
+
+// 历史版本，仅作为思路的参考保留，不对外暴露
+#[allow(dead_code, unused, clippy::all)]
 mod history;
-mod graph;
 
-// pub use linked_list::{ArenaList, Node};
\ No newline at end of file
+pub use error::{Error, Result};
+pub use union_find::UnionFind;
+pub use rng::Rng;
@@ -6,9 +6,18 @@ ArenaList，相当于对象池
 也可以用 nodes: Vec<NodeInfo<T>> 来管理，其中 NodeInfo{data, next_idx}
 
 holes 用来存放孔洞，出现孔洞后，后续的插入优先使用孔洞。孔洞的使用是用 栈 的方式
+
+可能失败的操作（get/insert/del/split）都有 try_ 版本，返回 Result<_, Error>
+
+LinkedList 持有 ArenaList 的 &mut 借用，需要保存/传输时，使用 save/load 与 LinkedListSnapshot 互转
 */
 
+use crate::error::{Error, Result};
+
 
+// 反序列化时检查 nexts、holes 中的 index，见 ArenaListRepr
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ArenaListRepr<T>"))]
 pub struct ArenaList<T> {
     pub nodes: Vec<Option<T>>,
     // 存放数据本身
@@ -18,8 +27,77 @@ pub struct ArenaList<T> {
     // 存放孔洞对应的 index
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ArenaListRepr<T> {
+    nodes: Vec<Option<T>>,
+    nexts: Vec<Option<usize>>,
+    holes: Vec<usize>,
+}
+
+// 数据可能被篡改或损坏：长度不一致、越界的 index、指向孔洞的 next、重复或非空的孔洞、
+// 被多个 next 指向的节点、成环的链表都返回错误，避免之后 panic 或死循环
+#[cfg(feature = "serde")]
+impl<T> TryFrom<ArenaListRepr<T>> for ArenaList<T> {
+    type Error = Error;
+
+    fn try_from(repr: ArenaListRepr<T>) -> Result<Self> {
+        let n = repr.nodes.len();
+        if repr.nexts.len() != n {
+            return Err(Error::OutOfRange(repr.nexts.len()));
+        }
+        let mut is_hole = vec![false; n];
+        for &idx in &repr.holes {
+            if idx >= n {
+                return Err(Error::OutOfRange(idx));
+            }
+            if is_hole[idx] || repr.nodes[idx].is_some() || repr.nexts[idx].is_some() {
+                return Err(Error::StaleIndex(idx));
+            }
+            is_hole[idx] = true;
+        }
+        // 每个节点最多被一个 next 指向，否则多个链表共用同一段，或者链表的尾部绕回自身
+        let mut has_prev = vec![false; n];
+        for (idx, &next_idx) in repr.nexts.iter().enumerate() {
+            let Some(next_idx) = next_idx else { continue };
+            if next_idx >= n {
+                return Err(Error::OutOfRange(next_idx));
+            }
+            if is_hole[next_idx] {
+                return Err(Error::StaleIndex(next_idx));
+            }
+            if has_prev[next_idx] {
+                return Err(Error::UnknownEdge(idx, next_idx));
+            }
+            has_prev[next_idx] = true;
+        }
+        // 从每个链表的头（没有前驱的节点）出发走到底，走不到的节点都在环上
+        let mut visited = vec![false; n];
+        for head in (0..n).filter(|&idx| !has_prev[idx]) {
+            let mut curr_idx = Some(head);
+            while let Some(idx) = curr_idx {
+                if visited[idx] {
+                    break;
+                }
+                visited[idx] = true;
+                curr_idx = repr.nexts[idx];
+            }
+        }
+        if let Some(start) = (0..n).find(|&idx| !visited[idx]) {
+            let mut cycle = vec![start];
+            let mut idx = repr.nexts[start].unwrap();
+            while idx != start {
+                cycle.push(idx);
+                idx = repr.nexts[idx].unwrap();
+            }
+            return Err(Error::Cyclic(cycle));
+        }
+        Ok(Self { nodes: repr.nodes, nexts: repr.nexts, holes: repr.holes })
+    }
+}
+
 impl<T> ArenaList<T> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             nodes: Vec::new(),
             nexts: Vec::new(),
@@ -28,7 +106,7 @@ impl<T> ArenaList<T> {
     }
 
     //     新增一个节点，并返回其索引号
-    fn make_node(&mut self, data: Option<T>) -> usize {
+    pub fn make_node(&mut self, data: Option<T>) -> usize {
         match self.holes.pop() {
             // 如果有空洞，新数据放到空洞上
             Some(new_idx) => {
@@ -46,6 +124,16 @@ impl<T> ArenaList<T> {
     }
 }
 
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self { Self::new() }
+}
+
+// LinkedList 的快照，持有数据本身，用于保存/传输
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkedListSnapshot<T> {
+    pub data: Vec<T>,
+}
+
 pub struct LinkedList<'a, T> {
     root: usize,
     //根节点id
@@ -53,7 +141,7 @@ pub struct LinkedList<'a, T> {
 }
 
 impl<'a, T> LinkedList<'a, T> {
-    fn from_vec(arena_list: &'a mut ArenaList<T>, vec1: Vec<T>) -> Self {
+    pub fn from_vec(arena_list: &'a mut ArenaList<T>, vec1: Vec<T>) -> Self {
         let dummy = arena_list.make_node(None);
         let mut prev = dummy;
         for data in vec1 {
@@ -68,115 +156,166 @@ impl<'a, T> LinkedList<'a, T> {
         }
     }
 
-    fn clear() {}
+    // 清空链表。节点全部变为孔洞，dummy 节点保留
+    pub fn clear(&mut self) {
+        let mut curr_idx = self.root;
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            self.owner.nexts[curr_idx] = None;
+            self.owner.nodes[next_idx] = None;
+            self.owner.holes.push(next_idx);
+            curr_idx = next_idx;
+        }
+        self.owner.nexts[curr_idx] = None;
+    }
 
 
-    fn to_vec(&self) -> Vec<&T> {
+    pub fn to_vec(&self) -> Vec<&T> {
         let mut res = Vec::new();
         let mut curr_idx = self.root;
-        loop {
-            match self.owner.nexts[curr_idx] {
-                Some(next_idx) => {
-                    // 应该不会出现对应 next 不为 None，但 nodes 为 None 的情况
-                    match &self.owner.nodes[next_idx] {
-                        Some(node_data) => res.push(node_data),
-                        None => break
-                    }
-                    curr_idx = next_idx;
-                }
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            // 应该不会出现对应 next 不为 None，但 nodes 为 None 的情况
+            match &self.owner.nodes[next_idx] {
+                Some(node_data) => res.push(node_data),
                 None => break
             }
+            curr_idx = next_idx;
         }
         res
     }
 
-    fn get(&self, mut num: usize) -> &Option<T> {
+    // 与 to_vec 相同，但遇到指向孔洞的 next 时返回错误，而不是截断
+    pub fn try_to_vec(&self) -> Result<Vec<&T>> {
+        let mut res = Vec::new();
         let mut curr_idx = self.root;
-        loop {
-            match self.owner.nexts[curr_idx] {
-                Some(next_idx) => {
-                    if num == 0 {
-                        return &self.owner.nodes[next_idx];
-                    }
-                    curr_idx = next_idx;
-                    num -= 1;
-                }
-                None => { return &None; }
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            match &self.owner.nodes[next_idx] {
+                Some(node_data) => res.push(node_data),
+                None => return Err(Error::StaleIndex(next_idx))
             }
+            curr_idx = next_idx;
         }
+        Ok(res)
     }
 
-    fn insert(&mut self, mut num: usize, data: T) {
-        let new_idx = self.owner.make_node(Some(data));
+    pub fn get(&self, mut num: usize) -> &Option<T> {
+        let mut curr_idx = self.root;
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            if num == 0 {
+                return &self.owner.nodes[next_idx];
+            }
+            curr_idx = next_idx;
+            num -= 1;
+        }
+        &None
+    }
+
+    pub fn try_get(&self, num: usize) -> Result<&T> {
         let mut curr_idx = self.root;
-        loop {
+        for _ in 0..=num {
             match self.owner.nexts[curr_idx] {
-                Some(next_idx) => {
-                    if num == 0 {
-                        self.owner.nexts[new_idx] = Some(next_idx);
-                        self.owner.nexts[curr_idx] = Some(new_idx);
-                        curr_idx = new_idx;
-                        break;
-                    }
-                    curr_idx = next_idx;
-                    num -= 1;
-                }
-                None => break
+                Some(next_idx) => curr_idx = next_idx,
+                None => return Err(Error::OutOfRange(num))
             }
         }
+        self.owner.nodes[curr_idx].as_ref().ok_or(Error::StaleIndex(curr_idx))
     }
 
+    // 插入到第 num 个位置。num 不小于链表长度时不插入（追加到末尾用 try_insert）
+    pub fn insert(&mut self, num: usize, data: T) {
+        let mut curr_idx = self.root;
+        for _ in 0..=num {
+            match self.owner.nexts[curr_idx] {
+                Some(next_idx) => curr_idx = next_idx,
+                None => return
+            }
+        }
+        let _ = self.try_insert(num, data);
+    }
 
-    fn del(&mut self, mut num: usize) -> bool {
+    // 插入到第 num 个位置，num 等于链表长度时插入到末尾，超出时返回错误
+    pub fn try_insert(&mut self, num: usize, data: T) -> Result<()> {
         let mut curr_idx = self.root;
-        loop {
+        for _ in 0..num {
             match self.owner.nexts[curr_idx] {
-                Some(next_idx) => {
-                    if num == 0 {
-                        self.owner.nexts[curr_idx] = self.owner.nexts[next_idx];
-                        self.owner.nexts[next_idx] = None;
-                        self.owner.nodes[next_idx] = None;
-                        self.owner.holes.push(next_idx);
-                        return true;
-                    }
-                    curr_idx = next_idx;
-                    num -= 1;
-                }
-                None => {
-                    return false;
-                }
+                Some(next_idx) => curr_idx = next_idx,
+                None => return Err(Error::OutOfRange(num))
             }
         }
+        // 先找到位置再分配节点，避免越界时在 arena 中留下无人引用的节点
+        let new_idx = self.owner.make_node(Some(data));
+        self.owner.nexts[new_idx] = self.owner.nexts[curr_idx];
+        self.owner.nexts[curr_idx] = Some(new_idx);
+        Ok(())
+    }
+
+
+    pub fn del(&mut self, num: usize) -> bool {
+        self.try_del(num).is_ok()
+    }
+
+    pub fn try_del(&mut self, mut num: usize) -> Result<()> {
+        let target = num;
+        let mut curr_idx = self.root;
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            if num == 0 {
+                self.owner.nexts[curr_idx] = self.owner.nexts[next_idx];
+                self.owner.nexts[next_idx] = None;
+                self.owner.nodes[next_idx] = None;
+                self.owner.holes.push(next_idx);
+                return Ok(());
+            }
+            curr_idx = next_idx;
+            num -= 1;
+        }
+        Err(Error::OutOfRange(target))
+    }
+
+    // 保存为快照，快照中按顺序存放链表的数据
+    pub fn save(&self) -> LinkedListSnapshot<T> where T: Clone {
+        LinkedListSnapshot { data: self.to_vec().into_iter().cloned().collect() }
+    }
+
+    // 从快照加载，在 arena_list 上新建一个链表
+    pub fn load(arena_list: &'a mut ArenaList<T>, snapshot: LinkedListSnapshot<T>) -> Self {
+        Self::from_vec(arena_list, snapshot.data)
     }
 }
 
 impl<'a, T> LinkedList<'a, T> {
     // 示例：如何操作多个 Linked List
     // 多个 Linked List 的节点存放在同一个 arena_list。只是不同的 LinkedList 对象的 root 节点不一样
-    fn split(&mut self, mut num: usize) -> LinkedList<T> {
+    pub fn split(&mut self, mut num: usize) -> LinkedList<'_, T> {
         let dummy = self.owner.make_node(None);
         let mut curr_idx = self.root;
-        loop {
-            match self.owner.nexts[curr_idx] {
-                Some(next_idx) => {
-                    if num == 0 {
-                        self.owner.nexts[dummy] = Some(next_idx);
-                        self.owner.nexts[curr_idx] = None;
-                        break;
-                    }
-                    curr_idx = next_idx;
-                    num -= 1;
-                }
-                None => { break; }
+        while let Some(next_idx) = self.owner.nexts[curr_idx] {
+            if num == 0 {
+                self.owner.nexts[dummy] = Some(next_idx);
+                self.owner.nexts[curr_idx] = None;
+                break;
             }
+            curr_idx = next_idx;
+            num -= 1;
         }
         LinkedList { root: dummy, owner: self.owner }
     }
+
+    // 与 split 相同，但 num 超出链表长度时返回错误。num 等于链表长度时，拆出一个空链表
+    pub fn try_split(&mut self, num: usize) -> Result<LinkedList<'_, T>> {
+        let mut curr_idx = self.root;
+        for _ in 0..num {
+            match self.owner.nexts[curr_idx] {
+                Some(next_idx) => curr_idx = next_idx,
+                None => return Err(Error::OutOfRange(num))
+            }
+        }
+        Ok(self.split(num))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::linked_list::{ArenaList, LinkedList};
+    use crate::Error;
 
     #[test]
     fn test1() {
@@ -200,12 +339,69 @@ mod tests {
         let mut arena_list = ArenaList::new();
         let vec1 = vec![1, 2, 3, 4, 5, 6];
         let mut linked_list1 = LinkedList::from_vec(&mut arena_list, vec1);
-        let mut linked_list2 = linked_list1.split(3);
+        let linked_list2 = linked_list1.split(3);
         println!("{:?}", linked_list2.to_vec());
         println!("{:?}", linked_list1.to_vec());
     //     颠倒过来会发生生命周期冲突，之后解决
     }
-}
 
+    #[test]
+    fn test_save_load() {
+        let mut arena_list = ArenaList::new();
+        let linked_list = LinkedList::from_vec(&mut arena_list, vec![1, 2, 3]);
+        let snapshot = linked_list.save();
 
+        let mut arena_list2 = ArenaList::new();
+        let mut linked_list2 = LinkedList::load(&mut arena_list2, snapshot);
+        assert_eq!(linked_list2.to_vec(), vec![&1, &2, &3]);
+        linked_list2.clear();
+        assert!(linked_list2.to_vec().is_empty());
+        assert_eq!(arena_list2.holes.len(), 3);
+    }
 
+    #[test]
+    fn test_try() {
+        let mut arena_list = ArenaList::new();
+        let mut linked_list = LinkedList::from_vec(&mut arena_list, vec![1, 2, 3]);
+        assert_eq!(linked_list.try_get(2), Ok(&3));
+        assert_eq!(linked_list.try_get(3), Err(Error::OutOfRange(3)));
+        assert_eq!(linked_list.try_insert(3, 4), Ok(()));
+        assert_eq!(linked_list.try_insert(9, 4), Err(Error::OutOfRange(9)));
+        assert_eq!(linked_list.try_del(0), Ok(()));
+        assert_eq!(linked_list.try_del(3), Err(Error::OutOfRange(3)));
+        assert_eq!(linked_list.try_to_vec(), Ok(vec![&2, &3, &4]));
+        // insert 在 num 不小于长度时什么也不做，追加要用 try_insert
+        linked_list.insert(3, 5);
+        assert_eq!(linked_list.to_vec(), vec![&2, &3, &4]);
+        assert!(linked_list.try_split(4).is_err());
+        let linked_list2 = linked_list.try_split(1).unwrap();
+        assert_eq!(linked_list2.to_vec(), vec![&3, &4]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut arena_list = ArenaList::new();
+        let linked_list = LinkedList::from_vec(&mut arena_list, vec![1, 2, 3]);
+        let json = serde_json::to_string(&linked_list.save()).unwrap();
+
+        let mut arena_list2: ArenaList<i32> = ArenaList::new();
+        let linked_list2 = LinkedList::load(&mut arena_list2, serde_json::from_str(&json).unwrap());
+        assert_eq!(linked_list2.to_vec(), vec![&1, &2, &3]);
+
+        // 长度不一致、越界、指向孔洞、重复的孔洞、共用后继、成环：反序列化失败，而不是得到一个之后会 panic 的链表
+        let bad_jsons = [
+            r#"{"nodes":[null,1],"nexts":[1],"holes":[]}"#,
+            r#"{"nodes":[null,1],"nexts":[5,null],"holes":[]}"#,
+            r#"{"nodes":[null,null],"nexts":[1,null],"holes":[1]}"#,
+            r#"{"nodes":[null,null],"nexts":[null,null],"holes":[1,1]}"#,
+            r#"{"nodes":[null,1,2],"nexts":[null,2,1],"holes":[]}"#,
+            r#"{"nodes":[null,1,2],"nexts":[1,2,1],"holes":[]}"#,
+        ];
+        for json in bad_jsons {
+            assert!(serde_json::from_str::<ArenaList<i32>>(json).is_err());
+        }
+        let json = serde_json::to_string(&arena_list2).unwrap();
+        assert!(serde_json::from_str::<ArenaList<i32>>(&json).is_ok());
+    }
+}
@@ -0,0 +1,106 @@
+/*
+小型伪随机数生成器（SplitMix64），crate 不引入依赖，需要随机性的算法共用
+- 同一个 seed 产生同样的序列，算法结果可以复现
+- 不是密码学安全的，只用于打乱顺序、打破平局、随机游走、采样等场合
+- 对外公开，调用方可以用同一个 Rng 生成自己的随机数（例如随机建图）
+*/
+
+use std::collections::HashMap;
+
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, n) 中的整数，n 需大于 0
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        // 用 128 位乘法映射到 [0, n)，比取模更均匀
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // [0, 1) 中的浮点数，取高 53 位
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // 按权重随机选一个下标，权重不是正数（含 NaN）的视为 0；全为 0 时返回 None
+    pub fn gen_weighted(&mut self, weights: &[f64]) -> Option<usize> {
+        let valid = |w: f64| if w > 0.0 && w.is_finite() { w } else { 0.0 };
+        let total: f64 = weights.iter().map(|&w| valid(w)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut r = self.next_f64() * total;
+        let mut last = None;
+        for (i, &w) in weights.iter().enumerate() {
+            let w = valid(w);
+            if w == 0.0 {
+                continue;
+            }
+            if r < w {
+                return Some(i);
+            }
+            r -= w;
+            last = Some(i);
+        }
+        // 浮点误差落在末尾时取最后一个有效下标
+        last
+    }
+
+    // 从 0..n 中不放回地选 k 个（k 大于 n 时全选），按选中的顺序返回
+    // 只对前 k 个位置做 Fisher-Yates，被交换过的位置记在 HashMap 中，O(k)，n 很大时也不分配 0..n
+    pub fn sample(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let k = k.min(n);
+        let mut swapped: HashMap<usize, usize> = HashMap::with_capacity(k);
+        let mut res = Vec::with_capacity(k);
+        for i in 0..k {
+            let j = i + self.gen_range(n - i);
+            let value_j = swapped.get(&j).copied().unwrap_or(j);
+            let value_i = swapped.get(&i).copied().unwrap_or(i);
+            swapped.insert(j, value_i);
+            res.push(value_j);
+        }
+        res
+    }
+
+    pub fn shuffle<V>(&mut self, values: &mut [V]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.gen_range(i + 1));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::Rng;
+
+    #[test]
+    fn test_sample() {
+        let mut rng = Rng::new(3);
+        // n 很大、k 很小：不重复、不越界
+        let picked = rng.sample(usize::MAX / 2, 5);
+        assert_eq!(picked.len(), 5);
+        assert_eq!(picked.iter().collect::<HashSet<_>>().len(), 5);
+        // k 不小于 n 时是 0..n 的一个排列
+        let mut all = rng.sample(10, 20);
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        assert!(rng.sample(0, 3).is_empty());
+        assert_eq!(Rng::new(7).sample(100, 10), Rng::new(7).sample(100, 10));
+    }
+}
@@ -0,0 +1,123 @@
+/*
+并查集（disjoint-set）
+- 路径压缩 + 按秩合并，单次操作近似 O(1)
+- 元素为 0..len() 的整数，可以用 push 追加新元素，与 Graph 新增节点的 index 对应
+- 只支持合并，不支持拆分。删除边/节点后需要重新构建
+*/
+
+
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    count: usize,
+    // 集合的个数
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            count: n,
+        }
+    }
+
+    // 追加一个单独成集合的元素，返回其编号
+    pub fn push(&mut self) -> usize {
+        let idx = self.parent.len();
+        self.parent.push(idx);
+        self.rank.push(0);
+        self.count += 1;
+        idx
+    }
+
+    pub fn len(&self) -> usize { self.parent.len() }
+
+    pub fn is_empty(&self) -> bool { self.parent.is_empty() }
+
+    // 集合的个数
+    pub fn count(&self) -> usize { self.count }
+
+    // 找到所在集合的代表元素。用循环而不是递归，避免长链栈溢出
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // 路径压缩：沿途节点直接指向 root
+        let mut curr = x;
+        while self.parent[curr] != root {
+            let next = self.parent[curr];
+            self.parent[curr] = root;
+            curr = next;
+        }
+        root
+    }
+
+    // 合并两个元素所在的集合。原本就在同一集合时返回 false
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        // 按秩合并：矮的树挂到高的树下面
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // 每个元素所在集合的编号，编号为 0..count()，按元素首次出现的顺序分配
+    pub fn component_ids(&mut self) -> Vec<usize> {
+        let mut root_to_id = vec![usize::MAX; self.len()];
+        let mut next_id = 0;
+        (0..self.len()).map(|x| {
+            let root = self.find(x);
+            if root_to_id[root] == usize::MAX {
+                root_to_id[root] = next_id;
+                next_id += 1;
+            }
+            root_to_id[root]
+        }).collect()
+    }
+
+    // 所有集合，每个集合内的元素按从小到大排列
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut res = vec![Vec::new(); self.count];
+        for (x, id) in self.component_ids().into_iter().enumerate() {
+            res[id].push(x);
+        }
+        res
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::union_find::UnionFind;
+
+    #[test]
+    fn test1() {
+        let mut uf = UnionFind::new(5);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(3, 4));
+        assert!(!uf.union(1, 0));
+        assert_eq!(uf.count(), 3);
+        assert!(uf.connected(0, 1));
+        assert!(!uf.connected(1, 3));
+        let new_idx = uf.push();
+        uf.union(new_idx, 2);
+        assert_eq!(uf.component_ids(), vec![0, 0, 1, 2, 2, 1]);
+        assert_eq!(uf.groups(), vec![vec![0, 1], vec![2, 5], vec![3, 4]]);
+    }
+}
@@ -1,5 +1,9 @@
-use rust_algo::{Node, ArenaList};
+use rust_algo::linked_list::{ArenaList, LinkedList};
 
 
 #[test]
-fn func1() {}
\ No newline at end of file
+fn func1() {
+    let mut arena_list = ArenaList::new();
+    let linked_list = LinkedList::from_vec(&mut arena_list, vec![1, 2, 3]);
+    assert_eq!(linked_list.to_vec(), vec![&1, &2, &3]);
+}